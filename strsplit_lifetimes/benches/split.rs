@@ -0,0 +1,91 @@
+// cargo bench, and cargo bench --features simd for the vectorized paths.
+//
+// every group times a delimiter that gets a fast path with the simd feature next to a
+// predicate that finds the exact same matches but always takes the scalar char-by-char route,
+// so the two numbers show what the fast path buys on the same input.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use strsplit::{ByteSplit, Needle, StrSplit};
+
+// roughly what a csv export looks like: short fields, the odd non-ascii one.
+fn csv(rows: usize) -> String {
+    let mut s = String::new();
+    for i in 0..rows {
+        s.push_str(&format!("{i},name{i},größe,{},2024-01-{:02}\n", i * 7 % 1000, i % 28 + 1));
+    }
+    s
+}
+
+fn bench<F: FnMut() -> usize>(name: &str, bytes: usize, mut f: F) {
+    // warm up, then run for a fixed amount of time and report the best round.
+    black_box(f());
+    let mut best = Duration::MAX;
+    let started = Instant::now();
+    while started.elapsed() < Duration::from_secs(1) {
+        let round = Instant::now();
+        black_box(f());
+        best = best.min(round.elapsed());
+    }
+    let gbps = bytes as f64 / best.as_secs_f64() / 1e9;
+    println!("{name:<40} {best:>12.2?} {gbps:>8.2} GB/s");
+}
+
+fn main() {
+    let haystack = csv(200_000);
+    let n = haystack.len();
+    println!("haystack: {} MB, simd: {}", n / 1_000_000, cfg!(feature = "simd"));
+
+    bench("char ','", n, || StrSplit::new(&haystack, ',').count());
+    bench("scalar |c| c == ','", n, || {
+        StrSplit::new(&haystack, |c: char| c == ',').count()
+    });
+
+    // lines are a few dozen bytes each, which is where vectorized search starts to pay off.
+    bench("char '\\n'", n, || StrSplit::new(&haystack, '\n').count());
+    bench("scalar |c| c == '\\n'", n, || {
+        StrSplit::new(&haystack, |c: char| c == '\n').count()
+    });
+
+    bench("char 'ö' (non-ascii)", n, || StrSplit::new(&haystack, 'ö').count());
+    bench("scalar |c| c == 'ö'", n, || {
+        StrSplit::new(&haystack, |c: char| c == 'ö').count()
+    });
+
+    bench("[',', '\\n']", n, || StrSplit::new(&haystack, [',', '\n']).count());
+    bench("scalar |c| matches!(c, ',' | '\\n')", n, || {
+        StrSplit::new(&haystack, |c: char| matches!(c, ',' | '\n')).count()
+    });
+
+    bench("[',', '\\n', '-']", n, || {
+        StrSplit::new(&haystack, [',', '\n', '-']).count()
+    });
+    bench("scalar |c| matches!(c, ',' | '\\n' | '-')", n, || {
+        StrSplit::new(&haystack, |c: char| matches!(c, ',' | '\n' | '-')).count()
+    });
+
+    bench("&str \"größe,\"", n, || StrSplit::new(&haystack, "größe,").count());
+    let needle = Needle::new("größe,");
+    bench("Needle \"größe,\"", n, || StrSplit::new(&haystack, &needle).count());
+
+    // the worst case for naive search: almost-matches everywhere.
+    let aaa = "a".repeat(1_000_000);
+    let needle = Needle::new("aaaaaaaaaaaaaaab");
+    bench("Needle \"a…ab\" in \"a…a\"", aaa.len(), || {
+        StrSplit::new(&aaa, &needle).count()
+    });
+
+    let bytes = haystack.as_bytes();
+    bench("u8 b','", n, || ByteSplit::from_bytes(bytes, b',').count());
+    bench("scalar |b| b == b','", n, || {
+        ByteSplit::from_bytes(bytes, |b: u8| b == b',').count()
+    });
+
+    bench("u8 b'\\n'", n, || ByteSplit::from_bytes(bytes, b'\n').count());
+    bench("scalar |b| b == b'\\n'", n, || {
+        ByteSplit::from_bytes(bytes, |b: u8| b == b'\n').count()
+    });
+
+    bench("&[u8] b\"e,2\"", n, || ByteSplit::from_bytes(bytes, b"e,2").count());
+}
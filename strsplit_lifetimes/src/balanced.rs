@@ -0,0 +1,215 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+
+use crate::Delimiter;
+#[cfg(any(feature = "alloc", test))]
+use crate::StrSplit;
+
+const BRACKETS: &[(char, char)] = &[('(', ')'), ('[', ']'), ('{', '}')];
+
+/// Wraps a delimiter so that it only matches outside of brackets (and quotes, if you want).
+///
+/// Splitting `f(a, g(b, c), [d, e])`'s arguments `a, g(b, c), [d, e]` on `Balanced::new(", ")`
+/// gives `a`, `g(b, c)` and `[d, e]`. By default `()`, `[]` and `{}` nest, see
+/// [`Balanced::pairs`] and [`Balanced::quotes`] to change that.
+///
+/// While splitting, brackets are only counted, so a stray or mismatched one just shifts where
+/// the fields end. Use [`Balanced::check`] or [`Balanced::split`] to get an error for input like
+/// that instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Balanced<'p, D> {
+    delimiter: D,
+    pairs: &'p [(char, char)],
+    quotes: &'p [char],
+}
+
+/// Why [`Balanced::check`] didn't like its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unbalanced {
+    /// A closing bracket at this byte offset that doesn't close the bracket that's open.
+    Unexpected(usize),
+    /// An opening bracket or quote at this byte offset that's never closed.
+    Unclosed(usize),
+}
+
+impl fmt::Display for Unbalanced {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Unbalanced::Unexpected(at) => write!(f, "unexpected closing bracket at byte {at}"),
+            Unbalanced::Unclosed(at) => write!(f, "bracket or quote at byte {at} is never closed"),
+        }
+    }
+}
+
+impl Error for Unbalanced {}
+
+impl<D> Balanced<'static, D> {
+    pub fn new(delimiter: D) -> Self {
+        Self {
+            delimiter,
+            pairs: BRACKETS,
+            quotes: &[],
+        }
+    }
+}
+
+impl<'p, D> Balanced<'p, D> {
+    /// Use these (open, close) pairs instead of `()`, `[]` and `{}`.
+    pub fn pairs<'q>(self, pairs: &'q [(char, char)]) -> Balanced<'q, D>
+    where
+        'p: 'q,
+    {
+        Balanced {
+            delimiter: self.delimiter,
+            pairs,
+            quotes: self.quotes,
+        }
+    }
+
+    /// Also skip over anything between two of the same one of these quote chars. Brackets in
+    /// quotes don't count.
+    pub fn quotes<'q>(self, quotes: &'q [char]) -> Balanced<'q, D>
+    where
+        'p: 'q,
+    {
+        Balanced {
+            delimiter: self.delimiter,
+            pairs: self.pairs,
+            quotes,
+        }
+    }
+
+    /// Makes sure every bracket and quote in `s` is closed, in the right order.
+    #[cfg(feature = "alloc")]
+    pub fn check(&self, s: &str) -> Result<(), Unbalanced> {
+        // the closing bracket we're waiting for, and where its opening one was.
+        let mut open: Vec<(char, usize)> = Vec::new();
+        let mut quote: Option<(char, usize)> = None;
+        for (i, c) in s.char_indices() {
+            if let Some((q, _)) = quote {
+                if c == q {
+                    quote = None;
+                }
+            } else if self.quotes.contains(&c) {
+                quote = Some((c, i));
+            } else if let Some(&(_, close)) = self.pairs.iter().find(|&&(o, _)| o == c) {
+                open.push((close, i));
+            } else if self.pairs.iter().any(|&(_, close)| close == c) {
+                match open.pop() {
+                    Some((close, _)) if close == c => {}
+                    _ => return Err(Unbalanced::Unexpected(i)),
+                }
+            }
+        }
+        match (quote, open.pop()) {
+            (Some((_, at)), _) | (None, Some((_, at))) => Err(Unbalanced::Unclosed(at)),
+            (None, None) => Ok(()),
+        }
+    }
+
+    /// [`Balanced::check`]s `haystack` and only then splits it.
+    #[cfg(feature = "alloc")]
+    pub fn split(self, haystack: &str) -> Result<StrSplit<'_, Self>, Unbalanced>
+    where
+        D: Delimiter,
+    {
+        self.check(haystack)?;
+        Ok(StrSplit::new(haystack, self))
+    }
+}
+
+// how deep into brackets and whether we're in quotes, as we walk forward through a haystack.
+#[derive(Debug, Default)]
+struct Nesting {
+    depth: usize,
+    quote: Option<char>,
+}
+
+impl Nesting {
+    fn step<D>(&mut self, balanced: &Balanced<'_, D>, c: char) {
+        if let Some(q) = self.quote {
+            if c == q {
+                self.quote = None;
+            }
+        } else if balanced.quotes.contains(&c) {
+            self.quote = Some(c);
+        } else if balanced.pairs.iter().any(|&(open, _)| open == c) {
+            self.depth += 1;
+        } else if balanced.pairs.iter().any(|&(_, close)| close == c) {
+            self.depth = self.depth.saturating_sub(1);
+        }
+    }
+
+    fn at_top(&self) -> bool {
+        self.depth == 0 && self.quote.is_none()
+    }
+}
+
+// each search starts at the start of a field, which is at depth 0. so we ask the inner delimiter
+// for its next match, walk up to where it starts while counting brackets, and take it if we're
+// not nested in anything there. if we are, we ask again from just after where it started.
+impl<D> Delimiter for Balanced<'_, D>
+where
+    D: Delimiter,
+{
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        let mut nesting = Nesting::default();
+        let mut chars = s.char_indices().peekable();
+        let mut offset = 0;
+        loop {
+            let (start, end) = self.delimiter.find_next(&s[offset..])?;
+            let (start, end) = (offset + start, offset + end);
+            while let Some(&(i, c)) = chars.peek() {
+                if i >= start {
+                    break;
+                }
+                nesting.step(self, c);
+                chars.next();
+            }
+            if nesting.at_top() {
+                return Some((start, end));
+            }
+            offset = start + s[start..].chars().next()?.len_utf8();
+        }
+    }
+
+    // no find_prev: the default walks forward from the start of remainder, which is the only
+    // place we know the depth of.
+}
+
+#[test]
+fn arguments() {
+    let args = "a, g(b, c), [d, e], {f, (g)}";
+    let fields: Vec<_> = StrSplit::new(args, Balanced::new(", ")).collect();
+    assert_eq!(fields, vec!["a", "g(b, c)", "[d, e]", "{f, (g)}"]);
+    let fields: Vec<_> = StrSplit::new(args, Balanced::new(','))
+        .trim()
+        .rev()
+        .collect();
+    assert_eq!(fields, vec!["{f, (g)}", "[d, e]", "g(b, c)", "a"]);
+}
+
+#[test]
+fn custom_pairs_and_quotes() {
+    let balanced = Balanced::new(',').pairs(&[('<', '>')]).quotes(&['"']);
+    let fields: Vec<_> = StrSplit::new(r#"Vec<A, B>,"x, y",(c,d)"#, balanced).collect();
+    assert_eq!(fields, vec!["Vec<A, B>", r#""x, y""#, "(c", "d)"]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn check() {
+    let balanced = Balanced::new(',').quotes(&['\'']);
+    assert_eq!(balanced.check("f(a, [b]), ')'"), Ok(()));
+    assert_eq!(balanced.check("f(a, b]"), Err(Unbalanced::Unexpected(6)));
+    assert_eq!(balanced.check("a)"), Err(Unbalanced::Unexpected(1)));
+    assert_eq!(balanced.check("f(a, [b)"), Err(Unbalanced::Unexpected(7)));
+    assert_eq!(balanced.check("f(a, [b]"), Err(Unbalanced::Unclosed(1)));
+    assert_eq!(balanced.check("f(a, ')"), Err(Unbalanced::Unclosed(5)));
+
+    assert!(balanced.split("f(a, b], c").is_err());
+    let fields: Vec<_> = balanced.split("f(a, b), c").unwrap().collect();
+    assert_eq!(fields, vec!["f(a, b)", " c"]);
+}
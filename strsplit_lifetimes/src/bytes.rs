@@ -0,0 +1,194 @@
+use core::ops::Range;
+
+use crate::{last_match, sealed, Haystack, Searcher, StrSplit};
+
+/// A [`StrSplit`] over bytes instead of a `str`, for input that isn't guaranteed to be UTF-8.
+///
+/// Everything `StrSplit` can do works here too, the delimiters just implement
+/// [`ByteDelimiter`] instead of [`Delimiter`](crate::Delimiter).
+pub type ByteSplit<'haystack, D> = StrSplit<'haystack, D, [u8]>;
+
+/// [`Delimiter`](crate::Delimiter), but for splitting `[u8]`.
+pub trait ByteDelimiter {
+    fn find_next(&self, s: &[u8]) -> Option<(usize, usize)>;
+
+    // the last match in s. same deal as Delimiter::find_prev, the default walks forward.
+    fn find_prev(&self, s: &[u8]) -> Option<(usize, usize)> {
+        last_match(s, |s| self.find_next(s))
+    }
+}
+
+impl<D: ByteDelimiter> sealed::Searcher<[u8]> for D {}
+
+impl<D> Searcher<[u8]> for D
+where
+    D: ByteDelimiter,
+{
+    fn search_next(&mut self, haystack: &[u8]) -> Option<(usize, usize)> {
+        self.find_next(haystack)
+    }
+
+    fn search_prev(&mut self, haystack: &[u8]) -> Option<(usize, usize)> {
+        self.find_prev(haystack)
+    }
+}
+
+impl sealed::Haystack for [u8] {}
+
+// no chars here, every byte stands on its own.
+impl Haystack for [u8] {
+    type Unit = u8;
+
+    fn len(&self) -> usize {
+        <[u8]>::len(self)
+    }
+
+    fn slice(&self, range: Range<usize>) -> &Self {
+        &self[range]
+    }
+
+    fn first_len(&self) -> Option<usize> {
+        self.first().map(|_| 1)
+    }
+
+    fn last_len(&self) -> Option<usize> {
+        self.last().map(|_| 1)
+    }
+
+    fn is_boundary(&self, index: usize) -> bool {
+        index <= self.len()
+    }
+
+    fn trim_start_matches(&self, pred: fn(u8) -> bool) -> &Self {
+        let start = self.iter().position(|&b| !pred(b)).unwrap_or(self.len());
+        &self[start..]
+    }
+
+    fn trim_end_matches(&self, pred: fn(u8) -> bool) -> &Self {
+        let end = self.iter().rposition(|&b| !pred(b)).map_or(0, |i| i + 1);
+        &self[..end]
+    }
+
+    fn addr(&self) -> usize {
+        self.as_ptr() as usize
+    }
+}
+
+impl<'haystack, D> ByteSplit<'haystack, D> {
+    /// [`StrSplit::new`], for bytes.
+    pub fn from_bytes(haystack: &'haystack [u8], delimiter: D) -> Self {
+        Self::with_haystack(haystack, delimiter)
+    }
+
+    /// [`trim_matches`](StrSplit::trim_matches) with ASCII whitespace, there's no telling about
+    /// any other kind in raw bytes.
+    pub fn trim(self) -> Self {
+        self.trim_matches(|b| b.is_ascii_whitespace())
+    }
+}
+
+impl ByteDelimiter for u8 {
+    fn find_next(&self, s: &[u8]) -> Option<(usize, usize)> {
+        #[cfg(feature = "simd")]
+        return memchr::memchr(*self, s).map(|start| (start, start + 1));
+
+        #[cfg(not(feature = "simd"))]
+        s.iter().position(|b| b == self).map(|start| (start, start + 1))
+    }
+
+    fn find_prev(&self, s: &[u8]) -> Option<(usize, usize)> {
+        #[cfg(feature = "simd")]
+        return memchr::memrchr(*self, s).map(|start| (start, start + 1));
+
+        #[cfg(not(feature = "simd"))]
+        s.iter().rposition(|b| b == self).map(|start| (start, start + 1))
+    }
+}
+
+impl ByteDelimiter for &[u8] {
+    fn find_next(&self, s: &[u8]) -> Option<(usize, usize)> {
+        if self.is_empty() {
+            return Some((0, 0));
+        }
+        #[cfg(feature = "simd")]
+        return crate::simd::find_bytes(self, s).map(|start| (start, start + self.len()));
+
+        #[cfg(not(feature = "simd"))]
+        s.windows(self.len())
+            .position(|window| window == *self)
+            .map(|start| (start, start + self.len()))
+    }
+
+    fn find_prev(&self, s: &[u8]) -> Option<(usize, usize)> {
+        if self.is_empty() {
+            return Some((s.len(), s.len()));
+        }
+        #[cfg(feature = "simd")]
+        return crate::simd::rfind_bytes(self, s).map(|start| (start, start + self.len()));
+
+        #[cfg(not(feature = "simd"))]
+        s.windows(self.len())
+            .rposition(|window| window == *self)
+            .map(|start| (start, start + self.len()))
+    }
+}
+
+// so byte string literals like b"\r\n" work without having to turn them into a slice first.
+impl<const N: usize> ByteDelimiter for &[u8; N] {
+    fn find_next(&self, s: &[u8]) -> Option<(usize, usize)> {
+        self.as_slice().find_next(s)
+    }
+
+    fn find_prev(&self, s: &[u8]) -> Option<(usize, usize)> {
+        self.as_slice().find_prev(s)
+    }
+}
+
+impl<F> ByteDelimiter for F
+where
+    F: Fn(u8) -> bool,
+{
+    fn find_next(&self, s: &[u8]) -> Option<(usize, usize)> {
+        s.iter()
+            .position(|&b| self(b))
+            .map(|start| (start, start + 1))
+    }
+
+    fn find_prev(&self, s: &[u8]) -> Option<(usize, usize)> {
+        s.iter()
+            .rposition(|&b| self(b))
+            .map(|start| (start, start + 1))
+    }
+}
+
+#[test]
+fn not_utf8() {
+    let haystack = b"a\xff,b,\xfe\xfd";
+    let fields: Vec<_> = ByteSplit::from_bytes(haystack, b',').collect();
+    assert_eq!(fields, vec![&b"a\xff"[..], b"b", b"\xfe\xfd"]);
+    let fields: Vec<_> = ByteSplit::from_bytes(haystack, b',').rev().collect();
+    assert_eq!(fields, vec![&b"\xfe\xfd"[..], b"b", b"a\xff"]);
+}
+
+#[test]
+fn byte_string_delimiter() {
+    let fields: Vec<_> = ByteSplit::from_bytes(b"a\r\nb\r\n", b"\r\n").terminator().collect();
+    assert_eq!(fields, vec![&b"a"[..], b"b"]);
+    let fields: Vec<_> = ByteSplit::from_bytes(b"ab", &b""[..]).collect();
+    assert_eq!(fields, vec![&b""[..], b"a", b"b", b""]);
+}
+
+#[test]
+fn byte_predicate() {
+    let fields: Vec<_> = ByteSplit::from_bytes(b" a  b\t", |b: u8| b.is_ascii_whitespace())
+        .skip_empty()
+        .spans()
+        .collect();
+    assert_eq!(fields, vec![(1..2, &b"a"[..]), (4..5, b"b")]);
+}
+
+#[test]
+fn byte_trim() {
+    let fields: Vec<_> = ByteSplit::from_bytes(b" a |b ", b'|').trim().collect();
+    assert_eq!(fields, vec![&b"a"[..], b"b"]);
+}
@@ -0,0 +1,170 @@
+use crate::Delimiter;
+
+/// A string delimiter that ignores ASCII case, so `IgnoreAsciiCase("and")` splits on `"and"`,
+/// `"AND"` and `"And"` alike.
+///
+/// Only `A`-`Z` and `a`-`z` are treated as the same, everything else has to match exactly.
+/// ASCII letters are always a single byte either way, so the match has exactly the length of
+/// the needle.
+#[derive(Debug, Clone, Copy)]
+pub struct IgnoreAsciiCase<'n>(pub &'n str);
+
+impl IgnoreAsciiCase<'_> {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    // an ascii letter in the needle can only ever match an ascii letter, and anything else only
+    // matches the exact same byte, so every match starts and ends on a char boundary.
+    fn matches(&self, window: &[u8]) -> bool {
+        window.eq_ignore_ascii_case(self.0.as_bytes())
+    }
+}
+
+impl Delimiter for IgnoreAsciiCase<'_> {
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        if self.0.is_empty() {
+            return Some((0, 0));
+        }
+        s.as_bytes()
+            .windows(self.len())
+            .position(|window| self.matches(window))
+            .map(|start| (start, start + self.len()))
+    }
+
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        if self.0.is_empty() {
+            return Some((s.len(), s.len()));
+        }
+        s.as_bytes()
+            .windows(self.len())
+            .rposition(|window| self.matches(window))
+            .map(|start| (start, start + self.len()))
+    }
+
+    // only ascii letters are folded, so a match is the same length as the needle.
+    fn max_match_chars(&self) -> Option<usize> {
+        Some(self.0.chars().count())
+    }
+}
+
+#[cfg(feature = "unicode")]
+pub use unicode::IgnoreCase;
+
+#[cfg(feature = "unicode")]
+mod unicode {
+    use core::iter;
+
+    use caseless::Caseless;
+
+    use crate::Delimiter;
+
+    /// A string delimiter that matches regardless of case, using full Unicode case folding.
+    ///
+    /// That's more than lowercasing both sides: `"STRASSE"`, `"straße"` and `"Straße"` all match
+    /// each other, and so do the Kelvin sign `K` and `k`. A match can be longer or shorter (in
+    /// bytes, and in chars) than the needle, but it always covers whole chars of the haystack,
+    /// so the ranges handed to `StrSplit` slice the original text just fine.
+    ///
+    /// Text isn't normalized first, so a precomposed `é` doesn't match `e` plus a combining
+    /// accent.
+    #[derive(Debug, Clone)]
+    pub struct IgnoreCase {
+        folded: Vec<char>,
+    }
+
+    impl IgnoreCase {
+        /// Case folds `needle` once up front.
+        pub fn new(needle: &str) -> Self {
+            Self {
+                folded: needle.chars().default_case_fold().collect(),
+            }
+        }
+
+        // if the needle matches at the start of s, how many bytes of s it covers. every char of s
+        // is folded on its own and the folded needle has to end exactly where one of them does,
+        // "ß" folds to "ss" so it can't match just "s".
+        fn match_len(&self, s: &str) -> Option<usize> {
+            let mut needle = self.folded.iter();
+            for (i, c) in s.char_indices() {
+                for folded in iter::once(c).default_case_fold() {
+                    if needle.next() != Some(&folded) {
+                        return None;
+                    }
+                }
+                if needle.as_slice().is_empty() {
+                    return Some(i + c.len_utf8());
+                }
+            }
+            None
+        }
+    }
+
+    impl Delimiter for IgnoreCase {
+        fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+            if self.folded.is_empty() {
+                return Some((0, 0));
+            }
+            s.char_indices()
+                .find_map(|(start, _)| Some((start, start + self.match_len(&s[start..])?)))
+        }
+
+        fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+            if self.folded.is_empty() {
+                return Some((s.len(), s.len()));
+            }
+            s.char_indices()
+                .rev()
+                .find_map(|(start, _)| Some((start, start + self.match_len(&s[start..])?)))
+        }
+    }
+
+    impl Delimiter for &IgnoreCase {
+        fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+            (**self).find_next(s)
+        }
+
+        fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+            (**self).find_prev(s)
+        }
+    }
+
+    #[test]
+    fn full_case_folding() {
+        let and = IgnoreCase::new("and");
+        let fields: Vec<_> = crate::StrSplit::new("a AND b And c and d", &and)
+            .trim()
+            .collect();
+        assert_eq!(fields, vec!["a", "b", "c", "d"]);
+
+        let strasse = IgnoreCase::new("straße");
+        let fields: Vec<_> = crate::StrSplit::new("1STRASSE2Strasse3straße", &strasse).collect();
+        assert_eq!(fields, vec!["1", "2", "3", ""]);
+    }
+
+    #[test]
+    fn different_lengths() {
+        // the kelvin sign is 3 bytes, 'k' is 1.
+        let kelvin = IgnoreCase::new("k");
+        assert_eq!(kelvin.find_next("1\u{212A}2"), Some((1, 4)));
+        assert_eq!(kelvin.find_prev("1\u{212A}2k"), Some((5, 6)));
+
+        // "ß" folds to "ss", so a needle of "s" can't match half of it, but "ss" matches all of it
+        assert_eq!(IgnoreCase::new("s").find_next("aßb"), None);
+        assert_eq!(IgnoreCase::new("SS").find_next("aßb"), Some((1, 3)));
+        let fields: Vec<_> = crate::StrSplit::new("aßbSSc", IgnoreCase::new("ss"))
+            .rev()
+            .collect();
+        assert_eq!(fields, vec!["c", "b", "a"]);
+    }
+}
+
+#[test]
+fn ascii_case() {
+    let fields: Vec<_> = crate::StrSplit::new("a AND b And c and d", IgnoreAsciiCase(" and "))
+        .collect();
+    assert_eq!(fields, vec!["a", "b", "c", "d"]);
+    let fields: Vec<_> = crate::StrSplit::new("éXé", IgnoreAsciiCase("x")).rev().collect();
+    assert_eq!(fields, vec!["é", "é"]);
+    assert_eq!(IgnoreAsciiCase("É").find_next("é"), None);
+}
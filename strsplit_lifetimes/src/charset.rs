@@ -0,0 +1,114 @@
+use alloc::vec::Vec;
+
+use crate::Delimiter;
+
+/// A set of chars to split on, built once up front.
+///
+/// ASCII members live in a 128-bit bitmap so checking them is a single bit test. Anything
+/// outside ASCII goes into a sorted table that we binary search, which stays small for the
+/// kinds of separators people actually use.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CharSet {
+    ascii: u128,
+    other: Vec<char>,
+}
+
+impl CharSet {
+    /// Builds a set out of every char in `chars`.
+    pub fn new(chars: &str) -> Self {
+        chars.chars().collect()
+    }
+
+    /// Adds `c` to the set.
+    pub fn insert(&mut self, c: char) {
+        if c.is_ascii() {
+            self.ascii |= 1 << c as u32;
+        } else if let Err(at) = self.other.binary_search(&c) {
+            self.other.insert(at, c);
+        }
+    }
+
+    /// Whether `c` is one of the chars we split on.
+    pub fn contains(&self, c: char) -> bool {
+        if c.is_ascii() {
+            self.ascii & (1 << c as u32) != 0
+        } else {
+            self.other.binary_search(&c).is_ok()
+        }
+    }
+
+    fn contains_byte(&self, b: u8) -> bool {
+        b.is_ascii() && self.ascii & (1 << b) != 0
+    }
+}
+
+impl FromIterator<char> for CharSet {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        let mut set = CharSet::default();
+        for c in iter {
+            set.insert(c);
+        }
+        set
+    }
+}
+
+impl Delimiter for CharSet {
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        if self.other.is_empty() {
+            // only ascii members: every byte of a multi-byte char is >= 0x80 so it can never
+            // hit the bitmap, which means we can look at raw bytes and skip utf-8 decoding.
+            s.bytes()
+                .position(|b| self.contains_byte(b))
+                .map(|start| (start, start + 1))
+        } else {
+            (|c: char| self.contains(c)).find_next(s)
+        }
+    }
+
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        if self.other.is_empty() {
+            s.bytes()
+                .rposition(|b| self.contains_byte(b))
+                .map(|start| (start, start + 1))
+        } else {
+            (|c: char| self.contains(c)).find_prev(s)
+        }
+    }
+
+    fn max_match_chars(&self) -> Option<usize> {
+        Some(1)
+    }
+}
+
+// so one set can be shared by several splitters without cloning it.
+impl Delimiter for &CharSet {
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        (**self).find_next(s)
+    }
+
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        (**self).find_prev(s)
+    }
+
+    fn max_match_chars(&self) -> Option<usize> {
+        (**self).max_match_chars()
+    }
+}
+
+#[test]
+fn ascii_members() {
+    let set = CharSet::new(",;|");
+    assert!(set.contains(';'));
+    assert!(!set.contains('a'));
+    let fields: Vec<_> = crate::StrSplit::new("a,b;c|dé", &set).collect();
+    assert_eq!(fields, vec!["a", "b", "c", "dé"]);
+}
+
+#[test]
+fn non_ascii_members() {
+    let set: CharSet = ['→', ',', '・'].into_iter().collect();
+    assert!(set.contains('・'));
+    assert!(!set.contains('é'));
+    let fields: Vec<_> = crate::StrSplit::new("a→b・c,d", &set).rev().collect();
+    assert_eq!(fields, vec!["d", "c", "b", "a"]);
+}
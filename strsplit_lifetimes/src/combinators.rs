@@ -0,0 +1,225 @@
+use crate::Delimiter;
+
+/// Ways to build a [`Delimiter`] out of other ones, available on all of them.
+///
+/// ```
+/// use strsplit::{DelimiterExt, StrSplit};
+///
+/// let fields: Vec<_> = StrSplit::new(r#"a, "b, c";;d"#, ','.or(';').not_within('"').greedy())
+///     .collect();
+/// assert_eq!(fields, vec!["a", r#" "b, c""#, "d"]);
+/// ```
+pub trait DelimiterExt: Delimiter + Sized {
+    /// Matches wherever either one does, whichever comes first. When both match at the same
+    /// spot the longer match wins.
+    fn or<B: Delimiter>(self, other: B) -> Or<Self, B> {
+        Or(self, other)
+    }
+
+    /// Matches a match of this one with a match of `next` right after it, as one match.
+    fn followed_by<B: Delimiter>(self, next: B) -> FollowedBy<Self, B> {
+        FollowedBy(self, next)
+    }
+
+    /// Only matches outside of regions that start and end with a match of `region`, like
+    /// `','.not_within('"')` for commas outside of quotes. A region that's never closed runs to
+    /// the end.
+    fn not_within<R: Delimiter>(self, region: R) -> NotWithin<Self, R> {
+        NotWithin(self, region)
+    }
+
+    /// Takes a run of matches right next to each other as one match, so `','.greedy()` splits
+    /// "a,,,b" into "a" and "b".
+    fn greedy(self) -> Greedy<Self> {
+        Greedy(self)
+    }
+}
+
+impl<D: Delimiter> DelimiterExt for D {}
+
+/// See [`DelimiterExt::or`].
+#[derive(Debug, Clone, Copy)]
+pub struct Or<A, B>(A, B);
+
+/// See [`DelimiterExt::followed_by`].
+#[derive(Debug, Clone, Copy)]
+pub struct FollowedBy<A, B>(A, B);
+
+/// See [`DelimiterExt::not_within`].
+#[derive(Debug, Clone, Copy)]
+pub struct NotWithin<D, R>(D, R);
+
+/// See [`DelimiterExt::greedy`].
+#[derive(Debug, Clone, Copy)]
+pub struct Greedy<D>(D);
+
+// leftmost, then longest. from the back it's the one that ends last, then longest, so "\r\n"
+// wins over "\n" from either end.
+impl<A, B> Delimiter for Or<A, B>
+where
+    A: Delimiter,
+    B: Delimiter,
+{
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        match (self.0.find_next(s), self.1.find_next(s)) {
+            (Some(a), Some(b)) if (b.0, a.1) < (a.0, b.1) => Some(b),
+            (Some(a), _) => Some(a),
+            (None, b) => b,
+        }
+    }
+
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        match (self.0.find_prev(s), self.1.find_prev(s)) {
+            (Some(a), Some(b)) if (b.1, a.0) > (a.1, b.0) => Some(b),
+            (Some(a), _) => Some(a),
+            (None, b) => b,
+        }
+    }
+
+    fn max_match_chars(&self) -> Option<usize> {
+        Some(self.0.max_match_chars()?.max(self.1.max_match_chars()?))
+    }
+}
+
+// every match of the first one that the second one doesn't pick up right away is retried one char
+// further along, so this is only quick when the first one doesn't match too often.
+impl<A, B> Delimiter for FollowedBy<A, B>
+where
+    A: Delimiter,
+    B: Delimiter,
+{
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        let mut offset = 0;
+        loop {
+            let (start, end) = self.0.find_next(&s[offset..])?;
+            let (start, end) = (offset + start, offset + end);
+            if let Some((0, next_end)) = self.1.find_next(&s[end..]) {
+                return Some((start, end + next_end));
+            }
+            offset = start + s[start..].chars().next()?.len_utf8();
+        }
+    }
+
+    fn max_match_chars(&self) -> Option<usize> {
+        Some(self.0.max_match_chars()? + self.1.max_match_chars()?)
+    }
+}
+
+// the start of a search is always outside of a region (that's where fields start), so we can
+// walk forward from there: anything before the next region is fair game, and then we skip to
+// where that region ends and go again. an empty match of `region` doesn't start one.
+impl<D, R> Delimiter for NotWithin<D, R>
+where
+    D: Delimiter,
+    R: Delimiter,
+{
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        let mut offset = 0;
+        loop {
+            let rest = &s[offset..];
+            let found = self.0.find_next(rest);
+            let region = self.1.find_next(rest).filter(|(start, end)| start < end);
+            match (found, region) {
+                (Some((start, end)), Some((open, _))) if start < open => {
+                    return Some((offset + start, offset + end))
+                }
+                (Some((start, end)), None) => return Some((offset + start, offset + end)),
+                (_, Some((_, open_end))) => {
+                    let (_, close_end) = self.1.find_next(&rest[open_end..])?;
+                    offset += open_end + close_end;
+                }
+                (None, None) => return None,
+            }
+        }
+    }
+
+    // no find_prev: which side of a region we're on is only known from the front.
+}
+
+impl<D> Delimiter for Greedy<D>
+where
+    D: Delimiter,
+{
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        let (start, mut end) = self.0.find_next(s)?;
+        if start == end {
+            return Some((start, end));
+        }
+        while let Some((0, more)) = self.0.find_next(&s[end..]) {
+            if more == 0 {
+                break;
+            }
+            end += more;
+        }
+        Some((start, end))
+    }
+
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        let (mut start, end) = self.0.find_prev(s)?;
+        if start == end {
+            return Some((start, end));
+        }
+        while let Some((before, before_end)) = self.0.find_prev(&s[..start]) {
+            if before_end != start || before == before_end {
+                break;
+            }
+            start = before;
+        }
+        Some((start, end))
+    }
+}
+
+#[cfg(test)]
+use crate::StrSplit;
+
+#[test]
+fn or() {
+    let fields: Vec<_> = StrSplit::new("a,b;c,;d", ','.or(';')).collect();
+    assert_eq!(fields, vec!["a", "b", "c", "", "d"]);
+    let fields: Vec<_> = StrSplit::new("a\r\nb\nc", "\n".or("\r\n")).rev().collect();
+    assert_eq!(fields, vec!["c", "b", "a"]);
+    let fields: Vec<_> = StrSplit::new("a\r\nb\nc", "\n".or("\r\n")).collect();
+    assert_eq!(fields, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn followed_by() {
+    let key_value = ':'.followed_by(char::is_whitespace);
+    let fields: Vec<_> = StrSplit::new("http://x: y z", key_value).collect();
+    assert_eq!(fields, vec!["http://x", "y z"]);
+    let fields: Vec<_> = StrSplit::new("a::b:c", ':'.followed_by(':'))
+        .rev()
+        .collect();
+    assert_eq!(fields, vec!["b:c", "a"]);
+}
+
+#[test]
+fn not_within() {
+    let fields: Vec<_> = StrSplit::new(r#"a,"b,c",d,"e"#, ','.not_within('"')).collect();
+    assert_eq!(fields, vec!["a", r#""b,c""#, "d", r#""e"#]);
+    let fields: Vec<_> = StrSplit::new(r#"a,"b,c",d"#, ','.not_within('"'))
+        .rev()
+        .collect();
+    assert_eq!(fields, vec!["d", r#""b,c""#, "a"]);
+    let fields: Vec<_> = StrSplit::new("a /* b c */ d", ' '.not_within("/*".or("*/"))).collect();
+    assert_eq!(fields, vec!["a", "/* b c */", "d"]);
+}
+
+#[test]
+fn greedy() {
+    let cases: [(&str, &[&str]); 4] = [
+        ("a,,b,c,,,", &["a", "b", "c", ""]),
+        (",,a", &["", "a"]),
+        ("", &[""]),
+        (",,,", &["", ""]),
+    ];
+    for (haystack, expected) in cases {
+        let fields: Vec<_> = StrSplit::new(haystack, ','.greedy()).collect();
+        assert_eq!(fields, expected);
+        let mut fields: Vec<_> = StrSplit::new(haystack, ','.greedy()).rev().collect();
+        fields.reverse();
+        assert_eq!(fields, expected);
+    }
+    let fields: Vec<_> = StrSplit::new("a -- b", " ".or("-").greedy()).collect();
+    assert_eq!(fields, vec!["a", "b"]);
+}
@@ -0,0 +1,180 @@
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::Delimiter;
+#[cfg(feature = "alloc")]
+use crate::StrSplit;
+
+/// Wraps a delimiter so that matches preceded by an escape char don't count.
+///
+/// With `Escaped::backslash('.')`, the key path `a.b\.c.d` splits into `a`, `b\.c` and `d`.
+/// A match is escaped if there's an odd number of escape chars right in front of it, so `\\.`
+/// is an escaped backslash followed by a real separator. Escapes are left in the fields, see
+/// [`Escaped::unescape`] and [`StrSplit::unescaped`] to get rid of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Escaped<D> {
+    delimiter: D,
+    escape: char,
+}
+
+impl<D> Escaped<D> {
+    pub fn new(delimiter: D, escape: char) -> Self {
+        Self { delimiter, escape }
+    }
+
+    /// [`Escaped::new`] with `\` as the escape char.
+    pub fn backslash(delimiter: D) -> Self {
+        Self::new(delimiter, '\\')
+    }
+
+    /// Removes the escape chars from `field`, keeping whatever each of them escaped.
+    ///
+    /// Only allocates if there's an escape char in there to begin with.
+    #[cfg(feature = "alloc")]
+    pub fn unescape<'a>(&self, field: &'a str) -> Cow<'a, str> {
+        if !field.contains(self.escape) {
+            return Cow::Borrowed(field);
+        }
+        let mut unescaped = String::with_capacity(field.len());
+        let mut chars = field.chars();
+        while let Some(c) = chars.next() {
+            if c == self.escape {
+                // a trailing escape with nothing after it is kept as is.
+                unescaped.push(chars.next().unwrap_or(c));
+            } else {
+                unescaped.push(c);
+            }
+        }
+        Cow::Owned(unescaped)
+    }
+
+    // whether the match starting at `start` in s has an odd number of escapes in front of it.
+    fn is_escaped(&self, s: &str, start: usize) -> bool {
+        s[..start]
+            .chars()
+            .rev()
+            .take_while(|&c| c == self.escape)
+            .count()
+            % 2
+            == 1
+    }
+}
+
+impl<D> Delimiter for Escaped<D>
+where
+    D: Delimiter,
+{
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        let mut offset = 0;
+        loop {
+            let (start, end) = self.delimiter.find_next(&s[offset..])?;
+            let (start, end) = (offset + start, offset + end);
+            if !self.is_escaped(s, start) {
+                return Some((start, end));
+            }
+            // look again from the char after where the escaped match started.
+            offset = start + s[start..].chars().next()?.len_utf8();
+        }
+    }
+
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        let mut limit = s.len();
+        loop {
+            let (start, end) = self.delimiter.find_prev(&s[..limit])?;
+            if !self.is_escaped(s, start) {
+                return Some((start, end));
+            }
+            // look again in front of the escaped match, or in front of the char before it if it
+            // was an empty match right at the end of what we looked at.
+            limit = if start < limit {
+                start
+            } else {
+                start - s[..start].chars().next_back()?.len_utf8()
+            };
+        }
+    }
+}
+
+/// [`StrSplit`] with an [`Escaped`] delimiter that hands out fields with the escapes removed,
+/// see [`StrSplit::unescaped`].
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct Unescaped<'haystack, D> {
+    split: StrSplit<'haystack, Escaped<D>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'haystack, D> StrSplit<'haystack, Escaped<D>> {
+    /// Hand out every field with its escape chars removed, see [`Escaped::unescape`].
+    pub fn unescaped(self) -> Unescaped<'haystack, D> {
+        Unescaped { split: self }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'haystack, D> Iterator for Unescaped<'haystack, D>
+where
+    D: Delimiter,
+{
+    type Item = Cow<'haystack, str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let field = self.split.next()?;
+        Some(self.split.delimiter.unescape(field))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<D> DoubleEndedIterator for Unescaped<'_, D>
+where
+    D: Delimiter,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let field = self.split.next_back()?;
+        Some(self.split.delimiter.unescape(field))
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn key_path() {
+    let path = r"a.b\.c.d";
+    let fields: Vec<_> = StrSplit::new(path, Escaped::backslash('.')).collect();
+    assert_eq!(fields, vec!["a", r"b\.c", "d"]);
+    let fields: Vec<_> = StrSplit::new(path, Escaped::backslash('.')).rev().collect();
+    assert_eq!(fields, vec!["d", r"b\.c", "a"]);
+    let fields: Vec<_> = StrSplit::new(path, Escaped::backslash('.'))
+        .unescaped()
+        .collect();
+    assert_eq!(fields, vec!["a", "b.c", "d"]);
+    assert!(matches!(fields[0], Cow::Borrowed(_)));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn escaped_escapes() {
+    let path = r"a\\.b\\\.c";
+    let fields: Vec<_> = StrSplit::new(path, Escaped::backslash('.')).collect();
+    assert_eq!(fields, vec![r"a\\", r"b\\\.c"]);
+    let fields: Vec<_> = StrSplit::new(path, Escaped::backslash('.'))
+        .unescaped()
+        .rev()
+        .collect();
+    assert_eq!(fields, vec![r"b\.c", r"a\"]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn any_delimiter() {
+    let fields: Vec<_> = StrSplit::new("a::b^::c::d", Escaped::new("::", '^'))
+        .unescaped()
+        .collect();
+    assert_eq!(fields, vec!["a", "b::c", "d"]);
+    let fields: Vec<_> = StrSplit::new("a b%  c", Escaped::new(' ', '%'))
+        .skip_empty()
+        .unescaped()
+        .collect();
+    assert_eq!(fields, vec!["a", "b ", "c"]);
+}
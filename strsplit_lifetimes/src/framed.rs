@@ -0,0 +1,364 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::pin::Pin;
+use std::str::Utf8Error;
+use std::task::{Context, Poll};
+
+use bytes::BytesMut;
+use futures_core::Stream;
+use futures_io::AsyncRead;
+use tokio_util::codec::Decoder;
+
+use crate::reader::RecordBuf;
+use crate::Delimiter;
+
+/// Why a frame couldn't be decoded.
+#[derive(Debug)]
+pub enum FrameError {
+    Io(io::Error),
+    /// More than this many bytes came in without a complete frame.
+    TooLong(usize),
+    InvalidUtf8(Utf8Error),
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameError::Io(e) => write!(f, "{e}"),
+            FrameError::TooLong(max) => write!(f, "frame is longer than {max} bytes"),
+            FrameError::InvalidUtf8(e) => write!(f, "frame is not UTF-8: {e}"),
+        }
+    }
+}
+
+impl Error for FrameError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FrameError::Io(e) => Some(e),
+            FrameError::TooLong(_) => None,
+            FrameError::InvalidUtf8(e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for FrameError {
+    fn from(e: io::Error) -> Self {
+        FrameError::Io(e)
+    }
+}
+
+/// A tokio [`Decoder`] that cuts frames out of a byte stream wherever a [`Delimiter`] matches.
+///
+/// Frames are `String`s without their delimiter. A match is taken as soon as more input can't
+/// change it, the same as with [`ReaderSplit`](crate::ReaderSplit), so `"PING\n"` decodes to
+/// `"PING"` right away. Unlike it, there's no empty frame after a delimiter at the very end,
+/// since that's how framed messages usually end.
+///
+/// Whatever `decode` is given is moved into the codec's own buffer, which remembers how much of
+/// it has been checked for UTF-8 and searched already, so a frame that comes in a bit at a time
+/// isn't looked at again from the start every time.
+#[derive(Debug, Clone)]
+pub struct DelimiterCodec<D> {
+    delimiter: D,
+    max_length: usize,
+    buf: RecordBuf,
+}
+
+impl<D> DelimiterCodec<D>
+where
+    D: Delimiter,
+{
+    pub fn new(delimiter: D) -> Self {
+        Self {
+            delimiter,
+            max_length: usize::MAX,
+            buf: RecordBuf::default(),
+        }
+    }
+
+    /// Give up with [`FrameError::TooLong`] once a frame is longer than `max_length` bytes, or
+    /// is bound to be from what's buffered so far, instead of buffering forever. A delimiter
+    /// that's only partly in doesn't count, so a frame of exactly `max_length` bytes is fine
+    /// however its delimiter is cut up.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    fn frame(&mut self, buf: &mut BytesMut, eof: bool) -> Result<Option<String>, FrameError> {
+        self.buf.extend(buf);
+        buf.clear();
+        let frame = match self.buf.next_match(&self.delimiter, eof) {
+            Ok(Some((start, _))) if start > self.max_length => {
+                return Err(FrameError::TooLong(self.max_length))
+            }
+            Ok(Some((start, end))) => self.buf.take(start, end),
+            // a delimiter that's only partly in yet doesn't count against the frame, so only look
+            // at where the next match could start, not at everything we've got.
+            Ok(None) if self.buf.min_len() > self.max_length => {
+                return Err(FrameError::TooLong(self.max_length))
+            }
+            Ok(None) if eof && self.buf.len() > self.max_length => {
+                return Err(FrameError::TooLong(self.max_length))
+            }
+            Ok(None) if eof && !self.buf.is_empty() => self.buf.take_rest(),
+            Ok(None) => {
+                // more input is on its way, make room for it.
+                self.buf.compact();
+                return Ok(None);
+            }
+            Err(e) => return Err(FrameError::InvalidUtf8(e)),
+        };
+        Ok(Some(self.buf.record(frame).to_owned()))
+    }
+}
+
+impl<D> Decoder for DelimiterCodec<D>
+where
+    D: Delimiter,
+{
+    type Item = String;
+    type Error = FrameError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<String>, FrameError> {
+        self.frame(buf, false)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<String>, FrameError> {
+        self.frame(buf, true)
+    }
+}
+
+/// A [`Stream`] of the frames in an [`AsyncRead`], decoded by a [`DelimiterCodec`].
+///
+/// This is for the futures ecosystem, with tokio you can use
+/// `tokio_util::codec::FramedRead` with a [`DelimiterCodec`] instead. The reader needs to be
+/// `Unpin`, so `Box::pin` it if it isn't. After an error the stream ends.
+#[derive(Debug)]
+pub struct FrameStream<R, D> {
+    reader: R,
+    codec: DelimiterCodec<D>,
+    buf: BytesMut,
+    eof: bool,
+    finished: bool,
+}
+
+impl<R, D> FrameStream<R, D>
+where
+    R: AsyncRead + Unpin,
+    D: Delimiter,
+{
+    pub fn new(reader: R, delimiter: D) -> Self {
+        Self::with_codec(reader, DelimiterCodec::new(delimiter))
+    }
+
+    pub fn with_codec(reader: R, codec: DelimiterCodec<D>) -> Self {
+        Self {
+            reader,
+            codec,
+            buf: BytesMut::new(),
+            eof: false,
+            finished: false,
+        }
+    }
+
+    /// See [`DelimiterCodec::max_length`].
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.codec.max_length = max_length;
+        self
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R, D> Stream for FrameStream<R, D>
+where
+    R: AsyncRead + Unpin,
+    D: Delimiter + Unpin,
+{
+    type Item = Result<String, FrameError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let mut chunk = [0; 4096];
+        while !this.finished {
+            let frame = if this.eof {
+                this.codec.decode_eof(&mut this.buf)
+            } else {
+                this.codec.decode(&mut this.buf)
+            };
+            match frame {
+                Ok(Some(frame)) => return Poll::Ready(Some(Ok(frame))),
+                Ok(None) if this.eof => this.finished = true,
+                Ok(None) => {}
+                Err(e) => {
+                    this.finished = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+            if this.eof {
+                break;
+            }
+            match Pin::new(&mut this.reader).poll_read(cx, &mut chunk) {
+                Poll::Ready(Ok(0)) => this.eof = true,
+                Poll::Ready(Ok(n)) => this.buf.extend_from_slice(&chunk[..n]),
+                Poll::Ready(Err(e)) if e.kind() == io::ErrorKind::Interrupted => {}
+                Poll::Ready(Err(e)) => {
+                    this.finished = true;
+                    return Poll::Ready(Some(Err(e.into())));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        Poll::Ready(None)
+    }
+}
+
+// hands out one byte per read, and makes every other read wait, to get frames and chars to
+// straddle reads.
+#[cfg(test)]
+struct Trickle<'a> {
+    bytes: &'a [u8],
+    pending: bool,
+}
+
+#[cfg(test)]
+impl AsyncRead for Trickle<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.pending = !self.pending;
+        if self.pending {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        let n = self.bytes.len().min(buf.len()).min(1);
+        buf[..n].copy_from_slice(&self.bytes[..n]);
+        self.bytes = &self.bytes[n..];
+        Poll::Ready(Ok(n))
+    }
+}
+
+#[cfg(test)]
+fn stream_frames<D: Delimiter + Unpin>(
+    input: &str,
+    delimiter: D,
+) -> Vec<Result<String, FrameError>> {
+    use futures::StreamExt;
+    let reader = Trickle {
+        bytes: input.as_bytes(),
+        pending: false,
+    };
+    futures::executor::block_on(FrameStream::new(reader, delimiter).collect())
+}
+
+#[cfg(test)]
+fn codec_frames(bytes: &[u8]) -> Vec<Result<String, FrameError>> {
+    let mut codec = DelimiterCodec::new('\n');
+    let mut buf = BytesMut::from(bytes);
+    let mut frames = Vec::new();
+    while let Some(frame) = codec.decode_eof(&mut buf).transpose() {
+        let error = frame.is_err();
+        frames.push(frame);
+        if error {
+            break;
+        }
+    }
+    frames
+}
+
+#[test]
+fn stream() {
+    let frames: Vec<_> = stream_frames("héllo\r\nwörld\r\n\r\nlast", "\r\n")
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(frames, vec!["héllo", "wörld", "", "last"]);
+    assert_eq!(stream_frames("a;b;", ';').len(), 2);
+    assert!(stream_frames("", ';').is_empty());
+}
+
+#[test]
+fn stream_errors() {
+    let reader = futures::io::Cursor::new("ok\nway too long\nok\n");
+    let stream = FrameStream::new(reader, '\n').max_length(4);
+    let frames: Vec<_> = futures::executor::block_on(futures::StreamExt::collect(stream));
+    assert!(matches!(&frames[..], [Ok(ok), Err(FrameError::TooLong(4))] if ok == "ok"));
+
+    let frames = codec_frames(b"ok\n\xff\n");
+    assert!(matches!(
+        &frames[..],
+        [Ok(_), Err(FrameError::InvalidUtf8(_))]
+    ));
+}
+
+#[test]
+fn complete_frame_right_away() {
+    let mut codec = DelimiterCodec::new('\n');
+    let mut buf = BytesMut::from("PING\n");
+    assert_eq!(codec.decode(&mut buf).unwrap().as_deref(), Some("PING"));
+    assert_eq!(codec.decode(&mut buf).unwrap(), None);
+
+    // a longer match could still start at the very end, so that one has to wait.
+    let mut codec = DelimiterCodec::new(crate::MultiDelimiter::new(["\n", "\n\n"]));
+    let mut buf = BytesMut::from("a\n");
+    assert_eq!(codec.decode(&mut buf).unwrap(), None);
+    buf.extend_from_slice(b"b");
+    assert_eq!(codec.decode(&mut buf).unwrap().as_deref(), Some("a"));
+}
+
+#[test]
+fn delimiter_across_reads_at_max_length() {
+    let mut codec = DelimiterCodec::new("\r\n").max_length(2);
+    let mut buf = BytesMut::from("ab\r");
+    assert_eq!(codec.decode(&mut buf).unwrap(), None);
+    buf.extend_from_slice(b"\ncd\r");
+    assert_eq!(codec.decode(&mut buf).unwrap().as_deref(), Some("ab"));
+    assert_eq!(codec.decode(&mut buf).unwrap(), None);
+    buf.extend_from_slice(b"\n");
+    assert_eq!(codec.decode(&mut buf).unwrap().as_deref(), Some("cd"));
+
+    let delimiter = crate::MultiDelimiter::new(["\n", "\n\n"]);
+    let mut codec = DelimiterCodec::new(delimiter).max_length(2);
+    let mut buf = BytesMut::from("ab\n");
+    assert_eq!(codec.decode(&mut buf).unwrap(), None);
+    buf.extend_from_slice(b"\n");
+    assert_eq!(codec.decode(&mut buf).unwrap().as_deref(), Some("ab"));
+
+    let mut codec = DelimiterCodec::new("\r\n").max_length(2);
+    let mut buf = BytesMut::from("abc\r");
+    assert!(matches!(
+        codec.decode(&mut buf),
+        Err(FrameError::TooLong(2))
+    ));
+}
+
+#[test]
+fn codec() {
+    let mut codec = DelimiterCodec::new("\r\n").max_length(8);
+    let mut buf = BytesMut::from("one\r");
+    assert_eq!(codec.decode(&mut buf).unwrap(), None);
+    buf.extend_from_slice(b"\ntwo\r\nthree");
+    assert_eq!(codec.decode(&mut buf).unwrap().as_deref(), Some("one"));
+    assert_eq!(codec.decode(&mut buf).unwrap().as_deref(), Some("two"));
+    assert_eq!(codec.decode(&mut buf).unwrap(), None);
+    assert_eq!(
+        codec.decode_eof(&mut buf).unwrap().as_deref(),
+        Some("three")
+    );
+    assert_eq!(codec.decode_eof(&mut buf).unwrap(), None);
+
+    // "12345678" could still be a frame, if the "9" turns out to be the start of a "\r\n".
+    buf.extend_from_slice(b"123456789");
+    assert_eq!(codec.decode(&mut buf).unwrap(), None);
+    buf.extend_from_slice(b"0");
+    assert!(matches!(
+        codec.decode(&mut buf),
+        Err(FrameError::TooLong(8))
+    ));
+}
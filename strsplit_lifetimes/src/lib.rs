@@ -1,16 +1,98 @@
 //#![warn(missing_debug_implementations, rust_2018_idioms, missing_docs)]
+// no_std unless you ask for std (the default). without alloc too you still get StrSplit and
+// every delimiter that doesn't need to allocate. the tests always get std, so they can use
+// Vec and friends whatever the features are. tests/no_std.rs checks the library on its own.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::fmt::Debug;
+use core::ops::Range;
+
+mod balanced;
+mod bytes;
+mod case;
+#[cfg(feature = "alloc")]
+mod charset;
+mod combinators;
+mod escaped;
+#[cfg(feature = "async")]
+mod framed;
+#[cfg(feature = "alloc")]
+mod multi;
+mod mutable;
+mod needle;
+#[cfg(feature = "alloc")]
+mod owned;
+mod quoted;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "regex")]
+mod regex;
+#[cfg(feature = "unicode")]
+mod segment;
+#[cfg(feature = "simd")]
+mod simd;
+
+pub use balanced::{Balanced, Unbalanced};
+pub use bytes::{ByteDelimiter, ByteSplit};
+#[cfg(feature = "unicode")]
+pub use case::IgnoreCase;
+pub use case::IgnoreAsciiCase;
+#[cfg(feature = "alloc")]
+pub use charset::CharSet;
+pub use combinators::{DelimiterExt, FollowedBy, Greedy, NotWithin, Or};
+pub use escaped::Escaped;
+#[cfg(feature = "alloc")]
+pub use escaped::Unescaped;
+#[cfg(feature = "async")]
+pub use framed::{DelimiterCodec, FrameError, FrameStream};
+#[cfg(feature = "alloc")]
+pub use multi::MultiDelimiter;
+pub use mutable::StrSplitMut;
+pub use needle::Needle;
+#[cfg(feature = "alloc")]
+pub use owned::{ArcStr, OwnedStrSplit};
+pub use quoted::{Escape, Quoted};
+#[cfg(feature = "alloc")]
+pub use quoted::Unquoted;
+#[cfg(feature = "std")]
+pub use reader::ReaderSplit;
+#[cfg(feature = "regex")]
+pub use regex::CaptureSplit;
+#[cfg(feature = "unicode")]
+pub use segment::{GraphemeBoundary, Whitespace, WordBoundary};
 
 // generally use anonymous lifetimes if you can.
 // usually you dont need multiple lifetimes, quite rare, comes up when you need to store multiple
 // references
 
+/// An iterator over the fields of a haystack, split at every match of a [`Delimiter`].
+///
+/// `H` is what's being split. That's `str` unless you ask for something else, see [`ByteSplit`]
+/// for splitting bytes.
 #[derive(Debug)]
-pub struct StrSplit<'haystack, D> {
+pub struct StrSplit<'haystack, D, H: ?Sized + Haystack = str> {
     // by specifing lifetime
     // we say that remainder and delimiter
     // live 'a long(the pointers are valid for that long).
-    remainder: Option<&'haystack str>, 
+    remainder: Option<&'haystack H>, 
+    // the whole thing we were given, so we can tell where in it remainder is.
+    haystack: &'haystack H,
     delimiter: D,
+    // whether the front (back) of remainder sits right where a delimiter match ended (started).
+    // an empty match is not allowed there, otherwise we'd split at the same spot forever.
+    matched_front: bool,
+    matched_back: bool,
+    // how many more fields we're allowed to hand out, if there's a cap (see limit).
+    limit: Option<usize>,
+    // whether fields keep the delimiter that ended them (see inclusive).
+    inclusive: bool,
+    // see terminator, skip_empty and trim_matches.
+    terminator: bool,
+    skip_empty: bool,
+    trim: Option<fn(H::Unit) -> bool>,
 }
 
 // str -> [char] (similar to)
@@ -36,13 +118,88 @@ impl<'haystack, D> StrSplit<'haystack, D> {
     // the pointers that we give in can live as long as they want, but they have to at least live
     // 'a time.
     // This means that we can only use StrSplit as long as the input strings are still valid.
+
+    /// Splits `haystack` at every match of `delimiter`, like [`str::split`].
     pub fn new(haystack: &'haystack str, delimiter: D) -> Self {
+        Self::with_haystack(haystack, delimiter)
+    }
+
+    /// [`trim_matches`](Self::trim_matches) with whitespace, so `"a , b"` split on `','` gives
+    /// `"a"` and `"b"`.
+    pub fn trim(self) -> Self {
+        self.trim_matches(char::is_whitespace)
+    }
+}
+
+impl<'haystack, D, H> StrSplit<'haystack, D, H>
+where
+    H: ?Sized + Haystack,
+{
+    fn with_haystack(haystack: &'haystack H, delimiter: D) -> Self {
         Self {
             remainder: Some(haystack),
+            haystack,
             delimiter,
+            matched_front: false,
+            matched_back: false,
+            limit: None,
+            inclusive: false,
+            terminator: false,
+            skip_empty: false,
+            trim: None,
+        }
+    }
+
+    /// Like [`str::splitn`]: hands out at most `n` more fields. Once it's down to the last one it
+    /// stops looking for delimiters and gives back the rest of the haystack untouched, so
+    /// `"key=value=with=equals"` with `limit(2)` gives `"key"` and `"value=with=equals"`.
+    ///
+    /// The cap is shared by both ends, so `limit(n).rev()` is [`str::rsplitn`]: it splits off the
+    /// last `n - 1` fields and gives back everything in front of them as one piece. With
+    /// `limit(0)` there are no fields at all.
+    pub fn limit(mut self, n: usize) -> Self {
+        self.limit = Some(n);
+        self
+    }
+
+    /// Like [`str::split_terminator`]: the delimiter ends a field instead of sitting between
+    /// two, so a delimiter at the very end doesn't leave an empty field after it. That's what you
+    /// want for line-terminated data, `"a\nb\n"` gives `"a"` and `"b"`.
+    pub fn terminator(mut self) -> Self {
+        self.terminator = true;
+        self
+    }
+
+    /// Don't hand out empty fields at all, so a run of delimiters counts as one and leading or
+    /// trailing delimiters don't give empty fields. `"  a   b "` split on `' '` gives `"a"` and
+    /// `"b"`. This is checked after trimming, so a field of only trimmed chars is skipped too.
+    pub fn skip_empty(mut self) -> Self {
+        self.skip_empty = true;
+        self
+    }
+
+    /// Strip every char (or byte, for [`ByteSplit`]) matching `pred` off both ends of each field
+    /// before handing it out.
+    pub fn trim_matches(mut self, pred: fn(H::Unit) -> bool) -> Self {
+        self.trim = Some(pred);
+        self
+    }
+
+    // limit(0) doesn't hand out anything, not even the rest.
+    fn out_of_fields(&self) -> bool {
+        self.limit == Some(0)
+    }
+
+    // the cap is on fields, not delimiters: when only one field is left it's all of remainder.
+    fn on_last_field(&self) -> bool {
+        self.limit == Some(1)
+    }
+
+    fn count_field(&mut self) {
+        if let Some(ref mut n) = self.limit {
+            *n -= 1;
         }
     }
-    
 }
 
 // use match if I can care about more than one pattern
@@ -56,33 +213,609 @@ impl<'haystack, D> StrSplit<'haystack, D> {
 // }
 
 
+/// Something [`StrSplit`] can split on: a `char`, a `&str`, a closure over chars, and so on.
 pub trait Delimiter {
+    /// The first match in `s`, as the byte offsets of where it starts and ends.
     fn find_next(&self, s: &str) -> Option<(usize, usize)>; 
+
+    /// Same as `find_next`, but gives back the *last* match in `s`.
+    ///
+    /// Delimiters that can't search backwards get this for free: it keeps calling `find_next`
+    /// and remembers the last hit. That walks the whole string, so override it if you can do
+    /// better.
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        last_match(s, |s| self.find_next(s))
+    }
+
+    // the most chars a match can ever cover, if there's a limit. StrSplit doesn't need this, it's
+    // for ReaderSplit, which only gets to see its input a piece at a time: once it has this many
+    // chars from where a match starts, more input can't change it, so a "\n" at the very end of
+    // what it's got can be handed out right away, and when more input comes in only the last few
+    // chars have to be looked at again instead of the whole record. so only give a number if
+    // whether something is a match depends on nothing but the chars it covers. a delimiter that
+    // looks at what came before it (like Escaped or Balanced do) or can match a run of any length
+    // has to stick with None.
+    fn max_match_chars(&self) -> Option<usize> {
+        None
+    }
 }
 
-// let x: StrSplit;
-// for part in x {
-// }
-impl<'haystack, D>  Iterator for StrSplit<'haystack, D> 
-where 
-    D: Delimiter,
+/// A [`Delimiter`] that gets to change itself while it searches.
+///
+/// [`StrSplit`] searches with `&mut` access to its delimiter, so it can keep state from one
+/// field to the next: rotate through a list of separators, count how many fields it's seen, or
+/// remember where it got to. Every [`Delimiter`] is one of these too, so you only implement this
+/// one when you actually need the `&mut self`.
+///
+/// Each call is one search, and `find_next` calls go front to back through the haystack. The
+/// default `find_prev` calls `find_next` once per match in the whole remainder, which probably
+/// isn't what a stateful delimiter wants when splitting from the back, so implement it yourself
+/// if you're going to use `next_back`.
+pub trait StatefulDelimiter {
+    fn find_next(&mut self, s: &str) -> Option<(usize, usize)>;
+
+    fn find_prev(&mut self, s: &str) -> Option<(usize, usize)> {
+        last_match(s, |s| self.find_next(s))
+    }
+}
+
+impl<D> StatefulDelimiter for D
+where
+    D: Delimiter + ?Sized,
 {
-    type Item = &'haystack str;
-    fn next(&mut self) -> Option<Self::Item> {
+    fn find_next(&mut self, s: &str) -> Option<(usize, usize)> {
+        Delimiter::find_next(self, s)
+    }
+
+    fn find_prev(&mut self, s: &str) -> Option<(usize, usize)> {
+        Delimiter::find_prev(self, s)
+    }
+}
+
+// Haystack and Searcher have to be public since they're in StrSplit's bounds, but they're only
+// plumbing: offset() and friends trust addr and slice to agree, so nobody outside gets to
+// implement them. str and [u8] are all there is.
+mod sealed {
+    pub trait Haystack {}
+    pub trait Searcher<H: ?Sized> {}
+}
+
+/// What [`StrSplit`] needs to know about the thing it is splitting, implemented for `str` and
+/// `[u8]` only.
+///
+/// Everything is in byte offsets, for `str` that means staying on char boundaries too.
+pub trait Haystack: Debug + sealed::Haystack {
+    /// What [`StrSplit::trim_matches`] looks at: `char` for `str`, `u8` for `[u8]`.
+    type Unit: Copy + Debug;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn slice(&self, range: Range<usize>) -> &Self;
+
+    // how many bytes the first (last) char takes up, None if there isn't one.
+    fn first_len(&self) -> Option<usize>;
+    fn last_len(&self) -> Option<usize>;
+
+    fn is_boundary(&self, index: usize) -> bool;
+
+    fn trim_start_matches(&self, pred: fn(Self::Unit) -> bool) -> &Self;
+    fn trim_end_matches(&self, pred: fn(Self::Unit) -> bool) -> &Self;
+
+    // where in memory this starts, so we can tell how far into the haystack a slice of it is.
+    fn addr(&self) -> usize;
+}
+
+impl sealed::Haystack for str {}
+
+impl Haystack for str {
+    type Unit = char;
+
+    fn len(&self) -> usize {
+        str::len(self)
+    }
+
+    fn slice(&self, range: Range<usize>) -> &Self {
+        &self[range]
+    }
+
+    fn first_len(&self) -> Option<usize> {
+        self.chars().next().map(char::len_utf8)
+    }
+
+    fn last_len(&self) -> Option<usize> {
+        self.chars().next_back().map(char::len_utf8)
+    }
+
+    fn is_boundary(&self, index: usize) -> bool {
+        self.is_char_boundary(index)
+    }
+
+    fn trim_start_matches(&self, pred: fn(char) -> bool) -> &Self {
+        str::trim_start_matches(self, pred)
+    }
+
+    fn trim_end_matches(&self, pred: fn(char) -> bool) -> &Self {
+        str::trim_end_matches(self, pred)
+    }
+
+    fn addr(&self) -> usize {
+        self.as_ptr() as usize
+    }
+}
+
+/// The glue between a [`Haystack`] and the trait its delimiters implement, [`Delimiter`] for
+/// `str` and [`ByteDelimiter`] for `[u8]`.
+///
+/// You get this for free by implementing one of those (or [`StatefulDelimiter`], for `str`).
+pub trait Searcher<H: ?Sized>: sealed::Searcher<H> {
+    fn search_next(&mut self, haystack: &H) -> Option<(usize, usize)>;
+    fn search_prev(&mut self, haystack: &H) -> Option<(usize, usize)>;
+}
+
+impl<D: StatefulDelimiter> sealed::Searcher<str> for D {}
+
+impl<D> Searcher<str> for D
+where
+    D: StatefulDelimiter,
+{
+    fn search_next(&mut self, haystack: &str) -> Option<(usize, usize)> {
+        self.find_next(haystack)
+    }
+
+    fn search_prev(&mut self, haystack: &str) -> Option<(usize, usize)> {
+        self.find_prev(haystack)
+    }
+}
+
+// a delimiter that matches the empty string (like "") would otherwise match at the start of
+// remainder every single time, and since remainder never shrinks we'd hand out "" forever.
+// so we follow str::split(""): an empty match is allowed anywhere except exactly where the last
+// match ended. if that's what we got, the field has to have at least one char in it, so we step
+// over one char and look again. this gives "", "a", "b", "c", "" for "abc", same as std.
+// the same goes for the other end of remainder if next_back already split there.
+fn next_delimiter<H, D>(
+    delimiter: &mut D,
+    remainder: &H,
+    matched_front: bool,
+    matched_back: bool,
+) -> Option<(usize, usize)>
+where
+    H: ?Sized + Haystack,
+    D: Searcher<H>,
+{
+    let (mut start, mut end) = checked(remainder, delimiter.search_next(remainder)?);
+    if matched_front && end == 0 {
+        let skip = remainder.first_len()?;
+        let rest = remainder.slice(skip..remainder.len());
+        let (rest_start, rest_end) = checked(rest, delimiter.search_next(rest)?);
+        (start, end) = (skip + rest_start, skip + rest_end);
+    }
+    if matched_back && start == remainder.len() {
+        return None;
+    }
+    Some((start, end))
+}
+
+// next_delimiter, but walking backwards from the end of remainder.
+fn prev_delimiter<H, D>(
+    delimiter: &mut D,
+    remainder: &H,
+    matched_front: bool,
+    matched_back: bool,
+) -> Option<(usize, usize)>
+where
+    H: ?Sized + Haystack,
+    D: Searcher<H>,
+{
+    let (mut start, mut end) = checked(remainder, delimiter.search_prev(remainder)?);
+    if matched_back && start == remainder.len() {
+        let skip = remainder.last_len()?;
+        let rest = remainder.slice(0..remainder.len() - skip);
+        (start, end) = checked(rest, delimiter.search_prev(rest)?);
+    }
+    if matched_front && end == 0 {
+        return None;
+    }
+    Some((start, end))
+}
+
+// a custom delimiter giving back a range that's backwards, out of bounds or in the middle of a
+// char would make us panic somewhere much less obvious, so catch that early in debug builds.
+fn checked<H: ?Sized + Haystack>(s: &H, (start, end): (usize, usize)) -> (usize, usize) {
+    debug_assert!(
+        start <= end && end <= s.len() && s.is_boundary(start) && s.is_boundary(end),
+        "Delimiter returned {:?} which is not a valid range of {:?}",
+        start..end,
+        s,
+    );
+    (start, end)
+}
+
+// what find_prev does by default, for Delimiter, StatefulDelimiter and ByteDelimiter alike: call
+// find_next over and over and keep the last hit. an empty match would give us the same answer
+// forever, so after one we step over a char (a byte for [u8]) before looking again.
+fn last_match<H: ?Sized + Haystack>(
+    s: &H,
+    mut find_next: impl FnMut(&H) -> Option<(usize, usize)>,
+) -> Option<(usize, usize)> {
+    let mut last = None;
+    let mut offset = 0;
+    while let Some((start, end)) = find_next(s.slice(offset..s.len())) {
+        last = Some((offset + start, offset + end));
+        if start == end {
+            match s.slice(offset + end..s.len()).first_len() {
+                Some(len) => offset += end + len,
+                None => break,
+            }
+        } else {
+            offset += end;
+        }
+    }
+    last
+}
+
+// one call to next or next_back, in byte offsets into the haystack: the field we hand out and the
+// delimiter match that ended it (None for the last field, which just runs to the end).
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Step {
+    field: Range<usize>,
+    delimiter: Option<Range<usize>>,
+    // whether there's no delimiter after the field, which makes it the last one in the haystack.
+    // an empty one of those is what terminator and inclusive leave out.
+    last: bool,
+}
+
+impl<'haystack, D, H> StrSplit<'haystack, D, H>
+where
+    H: ?Sized + Haystack,
+    D: Searcher<H>,
+{
+    // remainder always points into haystack, so where it starts tells us how far in we are.
+    fn offset(&self, s: &H) -> usize {
+        s.addr() - self.haystack.addr()
+    }
+
+    // remainder is all used up: it's the last field there is.
+    fn step_rest(&mut self) -> Option<Step> {
+        let rest = self.remainder.take()?;
+        let start = self.offset(rest);
+        Some(Step {
+            field: start..start + rest.len(),
+            delimiter: None,
+            last: !self.matched_back,
+        })
+    }
+
+    // everything the builder options do to a field once we've found it: trim it, and maybe
+    // throw it away. None means we don't hand this one out.
+    fn keep(&self, mut step: Step) -> Option<Step> {
+        // with a trailing delimiter there's an empty field after it, which in terminator mode we
+        // don't want. in inclusive mode it's never wanted, the delimiter already went out with the
+        // field in front of it (same as str::split_terminator and str::split_inclusive). an empty
+        // field *in front of* a delimiter is still a field, like the one "" split on "" gives.
+        let trailing = step.last && step.field.is_empty();
+        if trailing && (self.terminator || self.inclusive) {
+            return None;
+        }
+        if let Some(trim) = self.trim {
+            let field = self.haystack.slice(step.field.clone());
+            let trimmed = field.trim_start_matches(trim);
+            let start = step.field.start + (field.len() - trimmed.len());
+            step.field = start..start + trimmed.trim_end_matches(trim).len();
+        }
+        if self.skip_empty && step.field.is_empty() {
+            return None;
+        }
+        Some(step)
+    }
+
+    fn step_front(&mut self) -> Option<Step> {
+        loop {
+            let step = self.split_front()?;
+            if let Some(step) = self.keep(step) {
+                self.count_field();
+                return Some(step);
+            }
+        }
+    }
+
+    fn step_back(&mut self) -> Option<Step> {
+        loop {
+            let step = self.split_back()?;
+            if let Some(step) = self.keep(step) {
+                self.count_field();
+                return Some(step);
+            }
+        }
+    }
+
+    fn split_front(&mut self) -> Option<Step> {
+        if self.out_of_fields() {
+            return None;
+        }
+        if self.on_last_field() {
+            return self.step_rest();
+        }
         // ref mut => We want a mutable reference to self.remainder if it is Some
         // I want a mutable reference to the thing I am matching rather than get the thing I am
         // matching itself
         if let Some(ref mut remainder /* &mut &'a str */) = self.remainder /* Option<&'a str> */ {
-            if let Some((delim_start, delim_end)) = self.delimiter.find_next(remainder) {
-                let until_delimiter = &remainder[..delim_start];
-                *remainder = &remainder[delim_end..];
-                Some(until_delimiter)
+            if let Some((delim_start, delim_end)) = next_delimiter(
+                &mut self.delimiter,
+                remainder,
+                self.matched_front,
+                self.matched_back,
+            ) {
+                let start = remainder.addr() - self.haystack.addr();
+                let field_end = if self.inclusive { delim_end } else { delim_start };
+                *remainder = remainder.slice(delim_end..remainder.len());
+                self.matched_front = true;
+                // inclusive fields from the back end right after a delimiter too. if this one
+                // takes us up to where the back left off, that was the same delimiter, and there
+                // is nothing in between, not even an empty field.
+                if self.inclusive && self.matched_back && remainder.is_empty() {
+                    self.remainder = None;
+                }
+                Some(Step {
+                    field: start..start + field_end,
+                    delimiter: Some(start + delim_start..start + delim_end),
+                    last: false,
+                })
+            } else {
+                self.step_rest()
+            }
+        } else {
+            None
+        }
+    }
+
+    // same dance as split_front, just from the other end: find the last delimiter, hand out
+    // whatever comes after it and keep the part in front of it.
+    // since both ends shrink the same remainder, calls to next and next_back can be mixed and
+    // will never give out the same field twice.
+    fn split_back(&mut self) -> Option<Step> {
+        if self.out_of_fields() {
+            return None;
+        }
+        if self.on_last_field() {
+            return self.step_rest();
+        }
+        if self.inclusive {
+            return self.split_back_inclusive();
+        }
+        if let Some(ref mut remainder) = self.remainder {
+            if let Some((delim_start, delim_end)) = prev_delimiter(
+                &mut self.delimiter,
+                remainder,
+                self.matched_front,
+                self.matched_back,
+            ) {
+                let start = remainder.addr() - self.haystack.addr();
+                let end = start + remainder.len();
+                *remainder = remainder.slice(0..delim_start);
+                let last = !self.matched_back;
+                self.matched_back = true;
+                Some(Step {
+                    field: start + delim_end..end,
+                    delimiter: Some(start + delim_start..start + delim_end),
+                    last,
+                })
             } else {
-                self.remainder.take()
+                self.step_rest()
             }
         } else {
-            None 
+            None
+        }
+    }
+
+    // from the back, inclusive fields end in the delimiter that *ends* remainder (if there is
+    // one), so the field starts right after the delimiter before that.
+    fn split_back_inclusive(&mut self) -> Option<Step> {
+        let remainder = self.remainder?;
+        let start = self.offset(remainder);
+        let mut trailing = None;
+        let mut cut = prev_delimiter(
+            &mut self.delimiter,
+            remainder,
+            self.matched_front,
+            self.matched_back,
+        );
+        if let Some((delim_start, delim_end)) = cut {
+            if delim_start < delim_end && delim_end == remainder.len() {
+                trailing = Some(start + delim_start..start + delim_end);
+                cut = prev_delimiter(
+                    &mut self.delimiter,
+                    remainder.slice(0..delim_start),
+                    self.matched_front,
+                    true,
+                );
+            }
+        }
+        match cut {
+            Some((_, cut_end)) => {
+                self.remainder = Some(remainder.slice(0..cut_end));
+                let last = !self.matched_back && trailing.is_none();
+                self.matched_back = true;
+                Some(Step {
+                    field: start + cut_end..start + remainder.len(),
+                    delimiter: trailing,
+                    last,
+                })
+            }
+            None => {
+                let mut step = self.step_rest()?;
+                step.last &= trailing.is_none();
+                step.delimiter = trailing;
+                Some(step)
+            }
+        }
+    }
+
+    /// Like [`str::split_inclusive`]: every field keeps the delimiter that ended it, so
+    /// `"a\nb\n"` gives `"a\n"` and `"b\n"`.
+    pub fn inclusive(mut self) -> Self {
+        self.inclusive = true;
+        self
+    }
+
+    /// Turns this into an iterator that hands out the delimiters too, in between the fields,
+    /// see [`Interleaved`]. The pieces have to add up to the haystack, so fields never include
+    /// their delimiter and are never skipped or trimmed here, whatever
+    /// [`inclusive`](Self::inclusive), [`skip_empty`](Self::skip_empty) and
+    /// [`trim_matches`](Self::trim_matches) said.
+    pub fn interleaved(mut self) -> Interleaved<'haystack, D, H> {
+        self.inclusive = false;
+        self.skip_empty = false;
+        self.trim = None;
+        Interleaved {
+            split: self,
+            front: None,
+            back: None,
+        }
+    }
+
+    /// Turns this into an iterator over where each field sits in the haystack, see [`Spans`].
+    pub fn spans(self) -> Spans<'haystack, D, H> {
+        Spans {
+            split: self,
+            delimiter: None,
         }
+    }
+}
+
+// everything a StrSplit keeps track of except what it borrows, for the splitters that can't hold
+// on to a borrow of their haystack between calls (OwnedStrSplit, StrSplitMut). they keep this
+// around and make a StrSplit out of it again every time they want a field, so the splitting
+// itself all happens in one place.
+#[derive(Debug, Clone)]
+struct Detached {
+    // where remainder is in the haystack.
+    remainder: Option<Range<usize>>,
+    matched_front: bool,
+    matched_back: bool,
+    limit: Option<usize>,
+    inclusive: bool,
+    terminator: bool,
+    skip_empty: bool,
+    trim: Option<fn(char) -> bool>,
+}
+
+// lends a delimiter to one of those StrSplits.
+struct ByRef<'d, D>(&'d mut D);
+
+impl<D: StatefulDelimiter> sealed::Searcher<str> for ByRef<'_, D> {}
+
+impl<D: StatefulDelimiter> Searcher<str> for ByRef<'_, D> {
+    fn search_next(&mut self, haystack: &str) -> Option<(usize, usize)> {
+        self.0.find_next(haystack)
+    }
+
+    fn search_prev(&mut self, haystack: &str) -> Option<(usize, usize)> {
+        self.0.find_prev(haystack)
+    }
+}
+
+impl Detached {
+    fn new(len: usize) -> Self {
+        Self {
+            remainder: Some(0..len),
+            matched_front: false,
+            matched_back: false,
+            limit: None,
+            inclusive: false,
+            terminator: false,
+            skip_empty: false,
+            trim: None,
+        }
+    }
+
+    // the StrSplit builders, for the wrappers that keep a Detached. the rules for what each one
+    // means all live in StrSplit, these only record the setting.
+    fn limit(&mut self, n: usize) {
+        self.limit = Some(n);
+    }
+
+    fn inclusive(&mut self) {
+        self.inclusive = true;
+    }
+
+    fn terminator(&mut self) {
+        self.terminator = true;
+    }
+
+    fn skip_empty(&mut self) {
+        self.skip_empty = true;
+    }
+
+    fn trim_matches(&mut self, pred: fn(char) -> bool) {
+        self.trim = Some(pred);
+    }
+
+    // one call to next (or next_back) on a StrSplit that's in this state, and keep whatever
+    // state it ends up in. `haystack` is the haystack from `base` on, which only has to go as far
+    // as remainder does. ranges in and out are in the whole haystack all the same.
+    fn step<D>(
+        &mut self,
+        haystack: &str,
+        base: usize,
+        delimiter: &mut D,
+        back: bool,
+    ) -> Option<Step>
+    where
+        D: StatefulDelimiter,
+    {
+        let mut split = StrSplit {
+            remainder: self
+                .remainder
+                .clone()
+                .map(|range| &haystack[range.start - base..range.end - base]),
+            haystack,
+            delimiter: ByRef(delimiter),
+            matched_front: self.matched_front,
+            matched_back: self.matched_back,
+            limit: self.limit,
+            inclusive: self.inclusive,
+            terminator: self.terminator,
+            skip_empty: self.skip_empty,
+            trim: self.trim,
+        };
+        let step = if back {
+            split.step_back()
+        } else {
+            split.step_front()
+        };
+        self.remainder = split.remainder.map(|remainder| {
+            let start = base + split.offset(remainder);
+            start..start + remainder.len()
+        });
+        self.matched_front = split.matched_front;
+        self.matched_back = split.matched_back;
+        self.limit = split.limit;
+        let shift = |range: Range<usize>| base + range.start..base + range.end;
+        step.map(|step| Step {
+            field: shift(step.field),
+            delimiter: step.delimiter.map(shift),
+            last: step.last,
+        })
+    }
+}
+
+// let x: StrSplit;
+// for part in x {
+// }
+impl<'haystack, D, H>  Iterator for StrSplit<'haystack, D, H> 
+where 
+    H: ?Sized + Haystack,
+    D: Searcher<H>,
+{
+    type Item = &'haystack H;
+    fn next(&mut self) -> Option<Self::Item> {
+        let step = self.step_front()?;
+        Some(self.haystack.slice(step.field))
 
         //let rest = self.remainder;
         // We are allowed to set self.remainder to an empty string because
@@ -94,19 +827,244 @@ where
     }
 }
 
+impl<D, H> DoubleEndedIterator for StrSplit<'_, D, H>
+where
+    H: ?Sized + Haystack,
+    D: Searcher<H>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let step = self.step_back()?;
+        Some(self.haystack.slice(step.field))
+    }
+}
+
+/// Like [`StrSplit`], but also says where in the haystack each field is.
+///
+/// Every item is the byte range of the field in the haystack that was passed to
+/// [`StrSplit::new`], together with the field itself, so `&haystack[range] == field`.
+/// The range of the delimiter that ended the most recent field is available from
+/// [`Spans::delimiter`], which is handy for pointing at either one in an error message.
+#[derive(Debug)]
+pub struct Spans<'haystack, D, H: ?Sized + Haystack = str> {
+    split: StrSplit<'haystack, D, H>,
+    delimiter: Option<Range<usize>>,
+}
+
+impl<D, H: ?Sized + Haystack> Spans<'_, D, H> {
+    /// The byte range of the delimiter that was matched by the last call to `next` or
+    /// `next_back`. That's the one right after the field for `next` and the one right in front
+    /// of it for `next_back`. `None` if the last field wasn't cut off by a delimiter.
+    pub fn delimiter(&self) -> Option<Range<usize>> {
+        self.delimiter.clone()
+    }
+}
+
+impl<'haystack, D, H> Iterator for Spans<'haystack, D, H>
+where
+    H: ?Sized + Haystack,
+    D: Searcher<H>,
+{
+    type Item = (Range<usize>, &'haystack H);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let step = self.split.step_front()?;
+        self.delimiter = step.delimiter;
+        Some((step.field.clone(), self.split.haystack.slice(step.field)))
+    }
+}
+
+impl<D, H> DoubleEndedIterator for Spans<'_, D, H>
+where
+    H: ?Sized + Haystack,
+    D: Searcher<H>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let step = self.split.step_back()?;
+        self.delimiter = step.delimiter;
+        Some((step.field.clone(), self.split.haystack.slice(step.field)))
+    }
+}
+
+
+/// One piece of the haystack as handed out by [`Interleaved`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum Piece<'haystack, H: ?Sized = str> {
+    Field(&'haystack H),
+    Delim(&'haystack H),
+}
+
+// derive would want H: Clone, which str isn't, even though we only hold references.
+impl<H: ?Sized> Clone for Piece<'_, H> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<H: ?Sized> Copy for Piece<'_, H> {}
+
+/// Like [`StrSplit`], but the delimiters come out too.
+///
+/// Fields and the delimiters between them are handed out in the order they appear in the
+/// haystack, so gluing all of them back together gives the haystack again. That's why
+/// [`StrSplit::skip_empty`] and [`StrSplit::trim_matches`] don't apply to it, they would leave
+/// pieces out.
+#[derive(Debug)]
+pub struct Interleaved<'haystack, D, H: ?Sized + Haystack = str> {
+    split: StrSplit<'haystack, D, H>,
+    // a delimiter we found while looking for a field but didn't hand out yet, one per end.
+    front: Option<Range<usize>>,
+    back: Option<Range<usize>>,
+}
+
+impl<'haystack, D, H> Iterator for Interleaved<'haystack, D, H>
+where
+    H: ?Sized + Haystack,
+    D: Searcher<H>,
+{
+    type Item = Piece<'haystack, H>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let haystack = self.split.haystack;
+        if let Some(delimiter) = self.front.take() {
+            return Some(Piece::Delim(haystack.slice(delimiter)));
+        }
+        match self.split.step_front() {
+            Some(step) => {
+                self.front = step.delimiter;
+                Some(Piece::Field(haystack.slice(step.field)))
+            }
+            // the fields ran out, but next_back may still be holding on to the delimiter that
+            // sat between the two ends.
+            None => self.back.take().map(|delimiter| Piece::Delim(haystack.slice(delimiter))),
+        }
+    }
+}
+
+impl<D, H> DoubleEndedIterator for Interleaved<'_, D, H>
+where
+    H: ?Sized + Haystack,
+    D: Searcher<H>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let haystack = self.split.haystack;
+        if let Some(delimiter) = self.back.take() {
+            return Some(Piece::Delim(haystack.slice(delimiter)));
+        }
+        match self.split.step_back() {
+            Some(step) => {
+                self.back = step.delimiter;
+                Some(Piece::Field(haystack.slice(step.field)))
+            }
+            None => self.front.take().map(|delimiter| Piece::Delim(haystack.slice(delimiter))),
+        }
+    }
+}
 
 impl Delimiter for &str {
     fn find_next(&self, s: &str) -> Option<(usize, usize)> {
         s.find(self).map(|start| (start, start + self.len()))
     }
+
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        s.rfind(self).map(|start| (start, start + self.len()))
+    }
+
+    fn max_match_chars(&self) -> Option<usize> {
+        Some(self.chars().count())
+    }
 }
 
 impl Delimiter for char {
     fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        // utf-8 never has one char's encoding in the middle of another's, so looking for the
+        // raw bytes finds the same char and lets memchr do the walking.
+        #[cfg(feature = "simd")]
+        return simd::find_char(*self, s.as_bytes()).map(|start| (start, start + self.len_utf8()));
+
+        #[cfg(not(feature = "simd"))]
+        s.char_indices()
+            .find(|(_, c)| c == self)
+            .map(|(start, _)| (start, start + self.len_utf8()))
+    }
+
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        #[cfg(feature = "simd")]
+        return simd::rfind_char(*self, s.as_bytes()).map(|start| (start, start + self.len_utf8()));
+
+        #[cfg(not(feature = "simd"))]
         s.char_indices()
+            .rev()
             .find(|(_, c)| c == self)
             .map(|(start, _)| (start, start + self.len_utf8()))
     }
+
+    fn max_match_chars(&self) -> Option<usize> {
+        Some(1)
+    }
+}
+
+// any char predicate works as a delimiter too, so StrSplit::new(s, char::is_whitespace) does what
+// you'd expect. the match covers exactly one char, so we report its utf-8 length and not 1.
+// this needs Fn and not FnMut since find_next only gets &self.
+impl<F> Delimiter for F
+where
+    F: Fn(char) -> bool,
+{
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        s.char_indices()
+            .find(|&(_, c)| self(c))
+            .map(|(start, c)| (start, start + c.len_utf8()))
+    }
+
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        s.char_indices()
+            .rev()
+            .find(|&(_, c)| self(c))
+            .map(|(start, c)| (start, start + c.len_utf8()))
+    }
+
+    fn max_match_chars(&self) -> Option<usize> {
+        Some(1)
+    }
+}
+
+// split on any of a handful of chars, e.g. StrSplit::new(s, [',', ';', '|']).
+// for anything bigger than a few chars build a CharSet once instead, it doesn't walk the whole
+// list for every char of the haystack.
+impl<const N: usize> Delimiter for [char; N] {
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        self.as_slice().find_next(s)
+    }
+
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        self.as_slice().find_prev(s)
+    }
+
+    fn max_match_chars(&self) -> Option<usize> {
+        Some(1)
+    }
+}
+
+impl Delimiter for &[char] {
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        #[cfg(feature = "simd")]
+        if let Some(needles) = simd::Needles::new(self) {
+            return needles.find(s.as_bytes()).map(|start| (start, start + 1));
+        }
+        (|c: char| self.contains(&c)).find_next(s)
+    }
+
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        #[cfg(feature = "simd")]
+        if let Some(needles) = simd::Needles::new(self) {
+            return needles.rfind(s.as_bytes()).map(|start| (start, start + 1));
+        }
+        (|c: char| self.contains(&c)).find_prev(s)
+    }
+
+    fn max_match_chars(&self) -> Option<usize> {
+        Some(1)
+    }
 }
 
 pub fn until_char(s: &str, c: char) -> &'_ str {
@@ -115,6 +1073,19 @@ pub fn until_char(s: &str, c: char) -> &'_ str {
         .expect("StrSplit always gives at least one result")
 }
 
+/// Splits `s` at the first delimiter, like [`str::split_once`].
+pub fn split_once<D: Delimiter>(s: &str, delimiter: D) -> Option<(&'_ str, &'_ str)> {
+    let mut split = StrSplit::new(s, delimiter).limit(2);
+    Some((split.next()?, split.next()?))
+}
+
+/// Splits `s` at the last delimiter, like [`str::rsplit_once`].
+pub fn rsplit_once<D: Delimiter>(s: &str, delimiter: D) -> Option<(&'_ str, &'_ str)> {
+    let mut split = StrSplit::new(s, delimiter).limit(2);
+    let after = split.next_back()?;
+    Some((split.next_back()?, after))
+}
+
 #[test]
 fn until_char_test() {
     assert_eq!(until_char("hello world", 'o'), "hell");
@@ -132,4 +1103,352 @@ fn tail() {
     let haystack = "a b c d ";
     let letters: Vec<_> = StrSplit::new(haystack, " ").collect();
     assert_eq!(letters, vec!["a", "b", "c", "d", ""]);
-} 
+}
+
+#[test]
+fn last_field() {
+    assert_eq!(StrSplit::new("a.b.c", '.').next_back(), Some("c"));
+    assert_eq!(StrSplit::new("abc", '.').next_back(), Some("abc"));
+}
+
+#[test]
+fn reverse() {
+    let haystack = "a, b, c, ";
+    let letters: Vec<_> = StrSplit::new(haystack, ", ").rev().collect();
+    assert_eq!(letters, vec!["", "c", "b", "a"]);
+}
+
+#[test]
+fn mixed_ends() {
+    let mut split = StrSplit::new("a b c d", ' ');
+    assert_eq!(split.next(), Some("a"));
+    assert_eq!(split.next_back(), Some("d"));
+    assert_eq!(split.next_back(), Some("c"));
+    assert_eq!(split.next(), Some("b"));
+    assert_eq!(split.next(), None);
+    assert_eq!(split.next_back(), None);
+}
+
+#[test]
+fn default_find_prev() {
+    struct Dot;
+    impl Delimiter for Dot {
+        fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+            s.find('.').map(|start| (start, start + 1))
+        }
+    }
+    let fields: Vec<_> = StrSplit::new("a.b.c", Dot).rev().collect();
+    assert_eq!(fields, vec!["c", "b", "a"]);
+}
+
+#[test]
+fn predicate() {
+    let fields: Vec<_> = StrSplit::new("a b\tc\u{3000}d", char::is_whitespace).collect();
+    assert_eq!(fields, vec!["a", "b", "c", "d"]);
+
+    let fields: Vec<_> = StrSplit::new("x.y!z", |c: char| c.is_ascii_punctuation())
+        .rev()
+        .collect();
+    assert_eq!(fields, vec!["z", "y", "x"]);
+}
+
+#[test]
+fn predicate_multibyte_match() {
+    let fields: Vec<_> = StrSplit::new("añbéc", |c: char| !c.is_ascii()).collect();
+    assert_eq!(fields, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn char_array() {
+    let fields: Vec<_> = StrSplit::new("a,b;c|d", [',', ';', '|']).collect();
+    assert_eq!(fields, vec!["a", "b", "c", "d"]);
+
+    let delims: &[char] = &[',', 'é'];
+    let fields: Vec<_> = StrSplit::new("aébéc,d", delims).rev().collect();
+    assert_eq!(fields, vec!["d", "c", "b", "a"]);
+}
+
+#[test]
+fn empty_delimiter() {
+    let fields: Vec<_> = StrSplit::new("abc", "").collect();
+    assert_eq!(fields, "abc".split("").collect::<Vec<_>>());
+    let fields: Vec<_> = StrSplit::new("aé", "").rev().collect();
+    assert_eq!(fields, "aé".rsplit("").collect::<Vec<_>>());
+    let fields: Vec<_> = StrSplit::new("", "").collect();
+    assert_eq!(fields, vec!["", ""]);
+}
+
+#[test]
+fn empty_delimiter_mixed_ends() {
+    let mut split = StrSplit::new("abc", "");
+    assert_eq!(split.next(), Some(""));
+    assert_eq!(split.next_back(), Some(""));
+    assert_eq!(split.next(), Some("a"));
+    assert_eq!(split.next_back(), Some("c"));
+    assert_eq!(split.next(), Some("b"));
+    assert_eq!(split.next(), None);
+    assert_eq!(split.next_back(), None);
+}
+
+#[test]
+fn empty_match_after_delimiter() {
+    // matches "," or the empty string, like the regex ",?" would
+    struct MaybeComma;
+    impl Delimiter for MaybeComma {
+        fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+            Some(if s.starts_with(',') { (0, 1) } else { (0, 0) })
+        }
+    }
+    let fields: Vec<_> = StrSplit::new("a,b", MaybeComma).collect();
+    assert_eq!(fields, vec!["", "a", "b", ""]);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "not a valid range")]
+fn bad_delimiter() {
+    struct Backwards;
+    impl Delimiter for Backwards {
+        fn find_next(&self, _: &str) -> Option<(usize, usize)> {
+            Some((2, 1))
+        }
+    }
+    StrSplit::new("abc", Backwards).next();
+}
+
+#[test]
+fn limit() {
+    let fields: Vec<_> = StrSplit::new("key=value=with=equals", '=').limit(2).collect();
+    assert_eq!(fields, vec!["key", "value=with=equals"]);
+    let fields: Vec<_> = StrSplit::new("a b c", ' ').limit(5).collect();
+    assert_eq!(fields, vec!["a", "b", "c"]);
+    let fields: Vec<_> = StrSplit::new("a b c", ' ').limit(1).collect();
+    assert_eq!(fields, vec!["a b c"]);
+    assert_eq!(StrSplit::new("a b c", ' ').limit(0).next(), None);
+}
+
+#[test]
+fn limit_from_the_right() {
+    let fields: Vec<_> = StrSplit::new("a.b.c.d", '.').limit(3).rev().collect();
+    assert_eq!(fields, vec!["d", "c", "a.b"]);
+    assert_eq!(
+        StrSplit::new("a.b.c.d", '.').limit(3).rev().collect::<Vec<_>>(),
+        "a.b.c.d".rsplitn(3, '.').collect::<Vec<_>>(),
+    );
+}
+
+#[test]
+fn split_once_test() {
+    assert_eq!(split_once("key=value=x", '='), Some(("key", "value=x")));
+    assert_eq!(rsplit_once("key=value=x", '='), Some(("key=value", "x")));
+    assert_eq!(split_once("key", '='), None);
+    assert_eq!(rsplit_once("key", '='), None);
+}
+
+#[test]
+fn spans() {
+    let haystack = "ab, c,, d";
+    let spans: Vec<_> = StrSplit::new(haystack, ", ").spans().collect();
+    assert_eq!(spans, vec![(0..2, "ab"), (4..6, "c,"), (8..9, "d")]);
+    for (range, field) in spans {
+        assert_eq!(&haystack[range], field);
+    }
+}
+
+#[test]
+fn spans_delimiter() {
+    let mut spans = StrSplit::new("a->b->c", "->").spans();
+    assert_eq!(spans.delimiter(), None);
+    assert_eq!(spans.next(), Some((0..1, "a")));
+    assert_eq!(spans.delimiter(), Some(1..3));
+    assert_eq!(spans.next_back(), Some((6..7, "c")));
+    assert_eq!(spans.delimiter(), Some(4..6));
+    assert_eq!(spans.next(), Some((3..4, "b")));
+    assert_eq!(spans.delimiter(), None);
+    assert_eq!(spans.next(), None);
+}
+
+#[test]
+fn inclusive() {
+    for haystack in ["a\nb\n", "a\nb", "\n\na", "", "\n"] {
+        let fields: Vec<_> = StrSplit::new(haystack, '\n').inclusive().collect();
+        assert_eq!(fields, haystack.split_inclusive('\n').collect::<Vec<_>>());
+        let fields: Vec<_> = StrSplit::new(haystack, '\n').inclusive().rev().collect();
+        assert_eq!(fields, haystack.split_inclusive('\n').rev().collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn inclusive_mixed_ends() {
+    // every order of next and next_back, which split_inclusive can do too.
+    for haystack in ["a,b", "a,b,", ",a", ",,", "a", ""] {
+        for order in 0..16 {
+            let mut split = StrSplit::new(haystack, ',').inclusive();
+            let mut expected = haystack.split_inclusive(',');
+            for i in 0..4 {
+                if order >> i & 1 == 1 {
+                    assert_eq!(split.next_back(), expected.next_back(), "{haystack:?} {order:04b}");
+                } else {
+                    assert_eq!(split.next(), expected.next(), "{haystack:?} {order:04b}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn inclusive_spans() {
+    let mut spans = StrSplit::new("a, b, c", ", ").inclusive().spans();
+    assert_eq!(spans.next(), Some((0..3, "a, ")));
+    assert_eq!(spans.delimiter(), Some(1..3));
+    assert_eq!(spans.next_back(), Some((6..7, "c")));
+    assert_eq!(spans.delimiter(), None);
+    assert_eq!(spans.next_back(), Some((3..6, "b, ")));
+    assert_eq!(spans.delimiter(), Some(4..6));
+    assert_eq!(spans.next(), None);
+}
+
+#[test]
+fn interleaved() {
+    use Piece::*;
+    let pieces: Vec<_> = StrSplit::new("a+b-c", ['+', '-']).interleaved().collect();
+    assert_eq!(pieces, vec![Field("a"), Delim("+"), Field("b"), Delim("-"), Field("c")]);
+    let pieces: Vec<_> = StrSplit::new("a+b-", ['+', '-']).interleaved().rev().collect();
+    assert_eq!(pieces, vec![Field(""), Delim("-"), Field("b"), Delim("+"), Field("a")]);
+}
+
+#[test]
+fn interleaved_mixed_ends() {
+    use Piece::*;
+    let mut pieces = StrSplit::new("a,b", ',').interleaved();
+    assert_eq!(pieces.next_back(), Some(Field("b")));
+    assert_eq!(pieces.next(), Some(Field("a")));
+    assert_eq!(pieces.next(), Some(Delim(",")));
+    assert_eq!(pieces.next(), None);
+    assert_eq!(pieces.next_back(), None);
+}
+
+#[test]
+fn interleaved_gives_back_everything() {
+    use Piece::*;
+    let pieces: Vec<_> = StrSplit::new(" a,,b ", ',')
+        .skip_empty()
+        .trim()
+        .interleaved()
+        .collect();
+    assert_eq!(
+        pieces,
+        vec![Field(" a"), Delim(","), Field(""), Delim(","), Field("b ")]
+    );
+}
+
+#[test]
+fn empty_field_before_a_delimiter() {
+    let fields: Vec<_> = StrSplit::new("", "").terminator().rev().collect();
+    assert_eq!(fields, vec![""]);
+    let fields: Vec<_> = StrSplit::new("", "").inclusive().rev().collect();
+    assert_eq!(fields, vec![""]);
+    for haystack in ["", "ab"] {
+        let fields: Vec<_> = StrSplit::new(haystack, "").terminator().collect();
+        assert_eq!(fields, haystack.split_terminator("").collect::<Vec<_>>());
+        let fields: Vec<_> = StrSplit::new(haystack, "").inclusive().collect();
+        assert_eq!(fields, haystack.split_inclusive("").collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn terminator() {
+    for haystack in ["a.b.", "a.b", "a..", ".", ""] {
+        let fields: Vec<_> = StrSplit::new(haystack, '.').terminator().collect();
+        assert_eq!(fields, haystack.split_terminator('.').collect::<Vec<_>>());
+        let fields: Vec<_> = StrSplit::new(haystack, '.').terminator().rev().collect();
+        assert_eq!(fields, haystack.rsplit_terminator('.').collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn skip_empty() {
+    let fields: Vec<_> = StrSplit::new("  a   b ", ' ').skip_empty().collect();
+    assert_eq!(fields, vec!["a", "b"]);
+    let fields: Vec<_> = StrSplit::new("  a   b ", ' ').skip_empty().rev().collect();
+    assert_eq!(fields, vec!["b", "a"]);
+    assert_eq!(StrSplit::new(",,,", ',').skip_empty().next(), None);
+}
+
+#[test]
+fn skip_empty_limit() {
+    let fields: Vec<_> = StrSplit::new(",,a,,b,,c", ',').skip_empty().limit(2).collect();
+    assert_eq!(fields, vec!["a", ",b,,c"]);
+}
+
+#[test]
+fn trim() {
+    let fields: Vec<_> = StrSplit::new(" a , b ,c ", ',').trim().collect();
+    assert_eq!(fields, vec!["a", "b", "c"]);
+    let fields: Vec<_> = StrSplit::new("[a]|[b]", '|')
+        .trim_matches(|c| c == '[' || c == ']')
+        .collect();
+    assert_eq!(fields, vec!["a", "b"]);
+}
+
+#[test]
+fn whitespace_columns() {
+    let haystack = "name    size  kind \n";
+    let columns: Vec<_> = StrSplit::new(haystack, ' ')
+        .trim()
+        .skip_empty()
+        .spans()
+        .collect();
+    assert_eq!(columns, vec![(0..4, "name"), (8..12, "size"), (14..18, "kind")]);
+}
+
+#[test]
+fn string_haystack() {
+    let haystack = String::from("a b");
+    let letters: Vec<_> = StrSplit::new(&haystack, ' ').collect();
+    assert_eq!(letters, vec!["a", "b"]);
+}
+
+#[test]
+fn stateful_delimiter() {
+    // ",", then ";", then "," again, and so on.
+    struct Rotate<'d> {
+        delimiters: &'d [&'d str],
+        next: usize,
+    }
+    impl StatefulDelimiter for Rotate<'_> {
+        fn find_next(&mut self, s: &str) -> Option<(usize, usize)> {
+            let delimiter = self.delimiters[self.next % self.delimiters.len()];
+            let start = s.find(delimiter)?;
+            self.next += 1;
+            Some((start, start + delimiter.len()))
+        }
+    }
+    let rotate = Rotate { delimiters: &[",", ";"], next: 0 };
+    let fields: Vec<_> = StrSplit::new("a,b;c;d,e", rotate).collect();
+    assert_eq!(fields, vec!["a", "b", "c;d", "e"]);
+
+    // only every other ',' counts, wherever the count was left off by the last search.
+    struct EveryOther {
+        seen: usize,
+    }
+    impl StatefulDelimiter for EveryOther {
+        fn find_next(&mut self, s: &str) -> Option<(usize, usize)> {
+            for (i, _) in s.match_indices(',') {
+                self.seen += 1;
+                if self.seen == 2 {
+                    self.seen = 0;
+                    return Some((i, i + 1));
+                }
+            }
+            None
+        }
+    }
+    let fields: Vec<_> = StrSplit::new("a,b,c,d,e", EveryOther { seen: 0 }).collect();
+    assert_eq!(fields, vec!["a,b", "c,d", "e"]);
+    #[cfg(feature = "alloc")]
+    {
+        let fields: Vec<_> = OwnedStrSplit::new("a,b,c,d,e", EveryOther { seen: 0 }).collect();
+        assert_eq!(fields, ["a,b", "c,d", "e"]);
+    }
+}
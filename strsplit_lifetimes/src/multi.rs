@@ -0,0 +1,189 @@
+use alloc::collections::VecDeque;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::Delimiter;
+
+/// Splits on any of several string patterns in a single pass over the haystack.
+///
+/// The patterns are compiled into an Aho-Corasick automaton once, in [`MultiDelimiter::new`],
+/// and every `find_next` after that just walks the haystack through it.
+///
+/// When patterns overlap, the match that starts first wins. If several patterns match at that
+/// same position, the *longest* one wins, no matter the order they were listed in. So with
+/// `["\n", "\r\n"]` (or `["\r\n", "\n"]`) the text `"a\r\nb"` is split into `"a"` and `"b"`,
+/// and a `"\r\n"` is never broken up into a `"\r"` and a `"\n"`.
+#[derive(Debug, Clone)]
+pub struct MultiDelimiter {
+    states: Vec<State>,
+    // chars in the longest pattern.
+    max_chars: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+struct State {
+    // sorted by byte so we can binary search them.
+    next: Vec<(u8, usize)>,
+    fail: usize,
+    // how many bytes of text this state stands for.
+    depth: usize,
+    // length of the longest pattern that ends here, following the fail links too.
+    longest: Option<usize>,
+}
+
+const ROOT: usize = 0;
+
+impl MultiDelimiter {
+    /// Builds the automaton for `patterns`.
+    pub fn new<I, P>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<str>,
+    {
+        let mut states = vec![State::default()];
+        let mut max_chars = 0;
+
+        // first a plain trie of all the patterns...
+        for pattern in patterns {
+            let pattern = pattern.as_ref();
+            max_chars = max_chars.max(pattern.chars().count());
+            let mut at = ROOT;
+            for &b in pattern.as_bytes() {
+                at = match states[at].next.binary_search_by_key(&b, |&(b, _)| b) {
+                    Ok(i) => states[at].next[i].1,
+                    Err(i) => {
+                        let depth = states[at].depth + 1;
+                        states.push(State {
+                            depth,
+                            ..State::default()
+                        });
+                        let new = states.len() - 1;
+                        states[at].next.insert(i, (b, new));
+                        new
+                    }
+                };
+            }
+            states[at].longest = Some(pattern.len());
+        }
+
+        // ...then the fail links, breadth first so a state's fail target is always done
+        // before the state itself.
+        let mut queue: VecDeque<usize> = states[ROOT].next.iter().map(|&(_, s)| s).collect();
+        while let Some(at) = queue.pop_front() {
+            for i in 0..states[at].next.len() {
+                let (b, child) = states[at].next[i];
+                let mut fail = states[at].fail;
+                let fail = loop {
+                    if let Some(to) = step(&states, fail, b) {
+                        break to;
+                    }
+                    if fail == ROOT {
+                        break ROOT;
+                    }
+                    fail = states[fail].fail;
+                };
+                states[child].fail = fail;
+                // whatever ends at the fail target also ends here, and it can only be shorter.
+                if states[child].longest.is_none() {
+                    states[child].longest = states[fail].longest;
+                }
+                queue.push_back(child);
+            }
+        }
+
+        Self { states, max_chars }
+    }
+
+    fn advance(&self, mut at: usize, b: u8) -> usize {
+        loop {
+            if let Some(to) = step(&self.states, at, b) {
+                return to;
+            }
+            if at == ROOT {
+                return ROOT;
+            }
+            at = self.states[at].fail;
+        }
+    }
+}
+
+fn step(states: &[State], at: usize, b: u8) -> Option<usize> {
+    let next = &states[at].next;
+    next.binary_search_by_key(&b, |&(b, _)| b)
+        .ok()
+        .map(|i| next[i].1)
+}
+
+impl Delimiter for MultiDelimiter {
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        // the first match we see isn't necessarily the leftmost one: with "bc" and "abcd" we
+        // see "bc" end before "abcd" does. so keep the best one so far and only stop once no
+        // match that is still in progress could start at or before it.
+        let mut best: Option<(usize, usize)> = self.states[ROOT].longest.map(|_| (0, 0));
+        let mut at = ROOT;
+        for (i, &b) in s.as_bytes().iter().enumerate() {
+            at = self.advance(at, b);
+            let end = i + 1;
+            let state = &self.states[at];
+            if let Some((best_start, _)) = best {
+                if end - state.depth > best_start {
+                    break;
+                }
+            }
+            if let Some(len) = state.longest {
+                let start = end - len;
+                match best {
+                    Some((best_start, best_end))
+                        if start > best_start || (start == best_start && end <= best_end) => {}
+                    _ => best = Some((start, end)),
+                }
+            }
+        }
+        best
+    }
+
+    fn max_match_chars(&self) -> Option<usize> {
+        Some(self.max_chars)
+    }
+}
+
+impl Delimiter for &MultiDelimiter {
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        (**self).find_next(s)
+    }
+
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        (**self).find_prev(s)
+    }
+
+    fn max_match_chars(&self) -> Option<usize> {
+        (**self).max_match_chars()
+    }
+}
+
+#[test]
+fn line_endings() {
+    let delim = MultiDelimiter::new(["\n", "\r\n", "||"]);
+    let fields: Vec<_> = crate::StrSplit::new("a\r\nb\nc||d\r\n", &delim).collect();
+    assert_eq!(fields, vec!["a", "b", "c", "d", ""]);
+    let fields: Vec<_> = crate::StrSplit::new("a\r\nb\nc||d", &delim).rev().collect();
+    assert_eq!(fields, vec!["d", "c", "b", "a"]);
+}
+
+#[test]
+fn leftmost_beats_longest() {
+    let delim = MultiDelimiter::new(["bc", "abcd", "xyz"]);
+    assert_eq!(delim.find_next("..abcd.."), Some((2, 6)));
+    assert_eq!(delim.find_next("..abce.."), Some((3, 5)));
+    assert_eq!(delim.find_next("..xybc.."), Some((4, 6)));
+    assert_eq!(delim.find_next("..ab.."), None);
+}
+
+#[test]
+fn longest_at_same_start() {
+    for patterns in [["a", "ab", "abc"], ["abc", "ab", "a"]] {
+        let delim = MultiDelimiter::new(patterns);
+        assert_eq!(delim.find_next("xabcx"), Some((1, 4)));
+        assert_eq!(delim.find_next("xabx"), Some((1, 3)));
+    }
+}
@@ -0,0 +1,184 @@
+#[cfg(test)]
+use crate::StrSplit;
+use crate::{Detached, StatefulDelimiter};
+
+/// Like [`StrSplit`](crate::StrSplit), but over a `&mut str`, handing out `&mut str` fields.
+///
+/// The fields never overlap, so they can all be held on to and changed at the same time, say
+/// to uppercase header names in place. Delimiters aren't part of any field (unless it's
+/// [`inclusive`](StrSplitMut::inclusive)), so they can't be changed through one.
+///
+/// There's no unsafe code in here: the remainder is split in two with `split_at_mut` every
+/// time, and the field part is handed out for good.
+#[derive(Debug)]
+pub struct StrSplitMut<'haystack, D> {
+    remainder: Option<&'haystack mut str>,
+    delimiter: D,
+    // with ranges in the whole haystack, of which we only still have remainder.
+    state: Detached,
+}
+
+impl<'haystack, D> StrSplitMut<'haystack, D>
+where
+    D: StatefulDelimiter,
+{
+    pub fn new(haystack: &'haystack mut str, delimiter: D) -> Self {
+        Self {
+            state: Detached::new(haystack.len()),
+            remainder: Some(haystack),
+            delimiter,
+        }
+    }
+
+    /// See [`StrSplit::limit`](crate::StrSplit::limit).
+    pub fn limit(mut self, n: usize) -> Self {
+        self.state.limit(n);
+        self
+    }
+
+    /// See [`StrSplit::inclusive`](crate::StrSplit::inclusive).
+    pub fn inclusive(mut self) -> Self {
+        self.state.inclusive();
+        self
+    }
+
+    /// See [`StrSplit::terminator`](crate::StrSplit::terminator).
+    pub fn terminator(mut self) -> Self {
+        self.state.terminator();
+        self
+    }
+
+    /// See [`StrSplit::skip_empty`](crate::StrSplit::skip_empty).
+    pub fn skip_empty(mut self) -> Self {
+        self.state.skip_empty();
+        self
+    }
+
+    /// See [`StrSplit::trim_matches`](crate::StrSplit::trim_matches).
+    pub fn trim_matches(mut self, pred: fn(char) -> bool) -> Self {
+        self.state.trim_matches(pred);
+        self
+    }
+
+    /// See [`StrSplit::trim`](crate::StrSplit::trim).
+    pub fn trim(self) -> Self {
+        self.trim_matches(char::is_whitespace)
+    }
+
+    // StrSplit does the looking on a shared reborrow of remainder and tells us which part of it
+    // is the field and which part is left. the field comes before what's left from the front
+    // and after it from the back, so one split_at_mut between them gives us both.
+    fn step(&mut self, back: bool) -> Option<&'haystack mut str> {
+        let remainder = self.remainder.take()?;
+        // where remainder starts in the whole haystack.
+        let base = self.state.remainder.as_ref()?.start;
+        let field = self
+            .state
+            .step(remainder, base, &mut self.delimiter, back)?
+            .field;
+        let field = field.start - base..field.end - base;
+        let Some(rest) = self.state.remainder.clone() else {
+            return Some(&mut remainder[field]);
+        };
+        let rest = rest.start - base..rest.end - base;
+        let (field, rest) = if back {
+            let (rest_part, field_part) = remainder.split_at_mut(field.start);
+            (&mut field_part[..field.len()], &mut rest_part[rest])
+        } else {
+            let (field_part, rest_part) = remainder.split_at_mut(rest.start);
+            (&mut field_part[field], &mut rest_part[..rest.len()])
+        };
+        self.remainder = Some(rest);
+        Some(field)
+    }
+}
+
+impl<'haystack, D> Iterator for StrSplitMut<'haystack, D>
+where
+    D: StatefulDelimiter,
+{
+    type Item = &'haystack mut str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.step(false)
+    }
+}
+
+impl<D> DoubleEndedIterator for StrSplitMut<'_, D>
+where
+    D: StatefulDelimiter,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.step(true)
+    }
+}
+
+// these are kept small enough to run under miri too: cargo +nightly miri test mutable
+
+#[test]
+fn uppercase_in_place() {
+    let mut headers = String::from("content-type: text/plain\r\nx-request-id: 7\r\n");
+    let mut names = Vec::new();
+    for line in StrSplitMut::new(&mut headers, "\r\n").terminator() {
+        if let Some(name) = StrSplitMut::new(line, ':').limit(2).next() {
+            names.push(name);
+        }
+    }
+    for name in &mut names {
+        name.make_ascii_uppercase();
+    }
+    assert_eq!(headers, "CONTENT-TYPE: text/plain\r\nX-REQUEST-ID: 7\r\n");
+}
+
+#[test]
+fn disjoint_from_both_ends() {
+    let mut haystack = String::from("a,bb,,ccc,");
+    let mut split = StrSplitMut::new(&mut haystack, ',');
+    let a = split.next().unwrap();
+    let last = split.next_back().unwrap();
+    let ccc = split.next_back().unwrap();
+    let bb = split.next().unwrap();
+    assert_eq!((&*a, &*bb, &*ccc, &*last), ("a", "bb", "ccc", ""));
+    a.make_ascii_uppercase();
+    ccc.make_ascii_uppercase();
+    assert_eq!(split.next().as_deref(), Some(""));
+    assert_eq!(split.next(), None);
+    assert_eq!(haystack, "A,bb,,CCC,");
+}
+
+#[test]
+fn same_as_borrowed() {
+    for haystack in ["a,b,,c,", ",", "", "é, ö ,ü", "a,,b", ",,"] {
+        let mut owned = haystack.to_owned();
+        let fields: Vec<_> = StrSplitMut::new(&mut owned, ',').trim().rev().collect();
+        let expected: Vec<_> = StrSplit::new(haystack, ',').trim().rev().collect();
+        assert_eq!(fields, expected);
+
+        let fields: Vec<_> = StrSplitMut::new(&mut owned, "").inclusive().collect();
+        let expected: Vec<_> = StrSplit::new(haystack, "").inclusive().collect();
+        assert_eq!(fields, expected);
+
+        let fields: Vec<_> = StrSplitMut::new(&mut owned, ',')
+            .terminator()
+            .rev()
+            .collect();
+        let expected: Vec<_> = StrSplit::new(haystack, ',').terminator().rev().collect();
+        assert_eq!(fields, expected);
+
+        let fields: Vec<_> = StrSplitMut::new(&mut owned, ',')
+            .inclusive()
+            .rev()
+            .collect();
+        let expected: Vec<_> = StrSplit::new(haystack, ',').inclusive().rev().collect();
+        assert_eq!(fields, expected);
+
+        let fields: Vec<_> = StrSplitMut::new(&mut owned, ',').limit(0).collect();
+        assert!(fields.is_empty());
+    }
+    let mut haystack = String::from("a,,b");
+    let fields: Vec<_> = StrSplitMut::new(&mut haystack, ',')
+        .terminator()
+        .rev()
+        .collect();
+    assert_eq!(fields, ["b", "", "a"]);
+}
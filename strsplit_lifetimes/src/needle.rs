@@ -0,0 +1,332 @@
+use core::cmp;
+
+use crate::Delimiter;
+
+/// A string delimiter that is preprocessed once and then reused for every search.
+///
+/// `&str` as a delimiter goes through `str::find` from scratch every time `StrSplit` looks for
+/// the next field. A `Needle` works out everything it needs to know about the pattern up front,
+/// in [`Needle::new`], and uses the Two-Way algorithm (Crochemore and Perrin) to search. That
+/// keeps every search linear in the length of the haystack with constant extra space, even for
+/// nasty inputs like looking for `"aaab"` in `"aaaaaaaa…"`.
+#[derive(Debug, Clone)]
+pub struct Needle<'n> {
+    needle: &'n [u8],
+    // the critical factorization: needle[..crit_pos] and needle[crit_pos..] (and crit_pos_back
+    // for searching backwards).
+    crit_pos: usize,
+    crit_pos_back: usize,
+    period: usize,
+    // a cheap way to tell that a byte isn't in the needle at all: bit (b & 63) is set for every
+    // byte b of the needle.
+    byteset: u64,
+    // whether needle[..crit_pos] repeats with `period`. if it does we can remember how much of the
+    // needle already matched when we shift by period, otherwise we just shift further.
+    short_period: bool,
+}
+
+impl<'n> Needle<'n> {
+    /// Prepares `needle` for searching.
+    pub fn new(needle: &'n str) -> Self {
+        let needle = needle.as_bytes();
+        if needle.is_empty() {
+            // matches everywhere, find and rfind never get past their first line.
+            return Self {
+                needle,
+                crit_pos: 0,
+                crit_pos_back: 0,
+                period: 1,
+                byteset: 0,
+                short_period: false,
+            };
+        }
+        let (crit_pos_false, period_false) = maximal_suffix(needle, false);
+        let (crit_pos_true, period_true) = maximal_suffix(needle, true);
+        let (crit_pos, period) = if crit_pos_false > crit_pos_true {
+            (crit_pos_false, period_false)
+        } else {
+            (crit_pos_true, period_true)
+        };
+        let byteset = needle.iter().fold(0, |set, &b| set | 1 << (b & 63));
+
+        if needle[..crit_pos] == needle[period..period + crit_pos] {
+            let crit_pos_back = needle.len()
+                - cmp::max(
+                    reverse_maximal_suffix(needle, period, false),
+                    reverse_maximal_suffix(needle, period, true),
+                );
+            Self {
+                needle,
+                crit_pos,
+                crit_pos_back,
+                period,
+                byteset,
+                short_period: true,
+            }
+        } else {
+            // no useful period, so we can always shift by more than half the needle.
+            Self {
+                needle,
+                crit_pos,
+                crit_pos_back: crit_pos,
+                period: cmp::max(crit_pos, needle.len() - crit_pos) + 1,
+                byteset,
+                short_period: false,
+            }
+        }
+    }
+
+    fn maybe_in_needle(&self, b: u8) -> bool {
+        self.byteset & (1 << (b & 63)) != 0
+    }
+
+    fn find(&self, haystack: &[u8]) -> Option<usize> {
+        let needle = self.needle;
+        if needle.is_empty() {
+            return Some(0);
+        }
+        let mut position = 0;
+        // how much of the start of the needle is known to match at position already.
+        let mut memory = 0;
+        'search: loop {
+            let last = *haystack.get(position + needle.len() - 1)?;
+            if !self.maybe_in_needle(last) {
+                position += needle.len();
+                memory = 0;
+                continue;
+            }
+
+            // the right part first, from crit_pos forwards...
+            let start = if self.short_period {
+                cmp::max(self.crit_pos, memory)
+            } else {
+                self.crit_pos
+            };
+            for i in start..needle.len() {
+                if needle[i] != haystack[position + i] {
+                    position += i - self.crit_pos + 1;
+                    memory = 0;
+                    continue 'search;
+                }
+            }
+
+            // ...then the left part, from crit_pos backwards.
+            let start = if self.short_period { memory } else { 0 };
+            for i in (start..self.crit_pos).rev() {
+                if needle[i] != haystack[position + i] {
+                    position += self.period;
+                    if self.short_period {
+                        memory = needle.len() - self.period;
+                    }
+                    continue 'search;
+                }
+            }
+
+            return Some(position);
+        }
+    }
+
+    // find, mirrored: we look at windows ending at `end` and move end towards the start.
+    fn rfind(&self, haystack: &[u8]) -> Option<usize> {
+        let needle = self.needle;
+        if needle.is_empty() {
+            return Some(haystack.len());
+        }
+        let mut end = haystack.len();
+        // how much of the end of the needle is known to match, as an index into the needle.
+        let mut memory = needle.len();
+        'search: loop {
+            let start = end.checked_sub(needle.len())?;
+            if !self.maybe_in_needle(haystack[start]) {
+                end -= needle.len();
+                memory = needle.len();
+                continue;
+            }
+
+            // the left part first, from crit_pos_back backwards...
+            let crit = if self.short_period {
+                cmp::min(self.crit_pos_back, memory)
+            } else {
+                self.crit_pos_back
+            };
+            for i in (0..crit).rev() {
+                if needle[i] != haystack[start + i] {
+                    end -= self.crit_pos_back - i;
+                    memory = needle.len();
+                    continue 'search;
+                }
+            }
+
+            // ...then the right part, from crit_pos_back forwards.
+            let needle_end = if self.short_period {
+                memory
+            } else {
+                needle.len()
+            };
+            for i in self.crit_pos_back..needle_end {
+                if needle[i] != haystack[start + i] {
+                    end -= self.period;
+                    if self.short_period {
+                        memory = self.period;
+                    }
+                    continue 'search;
+                }
+            }
+
+            return Some(start);
+        }
+    }
+}
+
+// the maximal suffix of arr under one of the two orderings of bytes, as (where it starts, its
+// period). the later of the two starts is the critical factorization two-way needs.
+fn maximal_suffix(arr: &[u8], order_greater: bool) -> (usize, usize) {
+    let mut left = 0;
+    let mut right = 1;
+    let mut offset = 0;
+    let mut period = 1;
+
+    while let Some(&a) = arr.get(right + offset) {
+        let b = arr[left + offset];
+        if (a < b && !order_greater) || (a > b && order_greater) {
+            // the suffix is smaller, so everything up to here is one period.
+            right += offset + 1;
+            offset = 0;
+            period = right - left;
+        } else if a == b {
+            // still repeating the current period.
+            if offset + 1 == period {
+                right += offset + 1;
+                offset = 0;
+            } else {
+                offset += 1;
+            }
+        } else {
+            // the suffix is bigger, start over from here.
+            left = right;
+            right += 1;
+            offset = 0;
+            period = 1;
+        }
+    }
+    (left, period)
+}
+
+// maximal_suffix on the reversed needle, for searching backwards. we already know the period,
+// so we can stop as soon as we get to it.
+fn reverse_maximal_suffix(arr: &[u8], known_period: usize, order_greater: bool) -> usize {
+    let mut left = 0;
+    let mut right = 1;
+    let mut offset = 0;
+    let mut period = 1;
+    let n = arr.len();
+
+    while right + offset < n {
+        let a = arr[n - (1 + right + offset)];
+        let b = arr[n - (1 + left + offset)];
+        if (a < b && !order_greater) || (a > b && order_greater) {
+            right += offset + 1;
+            offset = 0;
+            period = right - left;
+        } else if a == b {
+            if offset + 1 == period {
+                right += offset + 1;
+                offset = 0;
+            } else {
+                offset += 1;
+            }
+        } else {
+            left = right;
+            right += 1;
+            offset = 0;
+            period = 1;
+        }
+        if period == known_period {
+            break;
+        }
+    }
+    left
+}
+
+impl Delimiter for Needle<'_> {
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        self.find(s.as_bytes())
+            .map(|start| (start, start + self.needle.len()))
+    }
+
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        self.rfind(s.as_bytes())
+            .map(|start| (start, start + self.needle.len()))
+    }
+
+    fn max_match_chars(&self) -> Option<usize> {
+        // the needle came from a &str, so every byte that isn't a continuation byte starts a char.
+        Some(self.needle.iter().filter(|&&b| b & 0xc0 != 0x80).count())
+    }
+}
+
+impl Delimiter for &Needle<'_> {
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        (**self).find_next(s)
+    }
+
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        (**self).find_prev(s)
+    }
+
+    fn max_match_chars(&self) -> Option<usize> {
+        (**self).max_match_chars()
+    }
+}
+
+#[test]
+fn same_as_str_find() {
+    // every haystack and needle over a tiny alphabet up to some length, which hits all the
+    // periodic and non-periodic cases plenty of times.
+    fn strings(alphabet: &[char], max_len: usize) -> Vec<String> {
+        let mut all = vec![String::new()];
+        let mut last = vec![String::new()];
+        for _ in 0..max_len {
+            last = last
+                .iter()
+                .flat_map(|s| alphabet.iter().map(move |&c| format!("{s}{c}")))
+                .collect();
+            all.extend(last.iter().cloned());
+        }
+        all
+    }
+    let haystacks = strings(&['a', 'b', 'c'], 7);
+    for needle in strings(&['a', 'b'], 5) {
+        let searcher = Needle::new(&needle);
+        for haystack in &haystacks {
+            let len = needle.len();
+            assert_eq!(
+                searcher.find_next(haystack),
+                haystack.find(&*needle).map(|start| (start, start + len)),
+                "find {needle:?} in {haystack:?}",
+            );
+            assert_eq!(
+                searcher.find_prev(haystack),
+                haystack.rfind(&*needle).map(|start| (start, start + len)),
+                "rfind {needle:?} in {haystack:?}",
+            );
+        }
+    }
+}
+
+#[test]
+fn split_with_needle() {
+    let needle = Needle::new("<sep>");
+    let fields: Vec<_> = crate::StrSplit::new("a<sep>b<se>c<sep>é", &needle).collect();
+    assert_eq!(fields, vec!["a", "b<se>c", "é"]);
+    let fields: Vec<_> = crate::StrSplit::new("a<sep>b<se>c<sep>é", &needle).rev().collect();
+    assert_eq!(fields, vec!["é", "b<se>c", "a"]);
+}
+
+#[test]
+fn adversarial() {
+    let haystack = "a".repeat(100_000) + "b";
+    let needle = Needle::new("aaaaaaaaaaaaaaaaaaaaab");
+    let fields: Vec<_> = crate::StrSplit::new(&haystack, &needle).collect();
+    assert_eq!(fields, vec![&haystack[..haystack.len() - 22], ""]);
+}
@@ -0,0 +1,272 @@
+use alloc::sync::Arc;
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, Range};
+
+#[cfg(test)]
+use crate::StrSplit;
+use crate::{Detached, StatefulDelimiter};
+
+/// Like [`StrSplit`](crate::StrSplit), but it owns the haystack, so it isn't tied to a borrow
+/// of it.
+///
+/// That means it can be returned from the function that built the string, or kept in a struct
+/// next to whatever else. The haystack goes into an `Arc<str>` (which is free for an `Arc<str>`
+/// and one copy for a `String` or `Box<str>`), and each field comes out as an [`ArcStr`]
+/// sharing it, so handing out fields doesn't copy anything either.
+///
+/// Splitting works exactly like [`StrSplit`](crate::StrSplit), it's the same code doing it.
+#[derive(Debug, Clone)]
+pub struct OwnedStrSplit<D> {
+    haystack: Arc<str>,
+    delimiter: D,
+    state: Detached,
+}
+
+/// A field handed out by [`OwnedStrSplit`]: a range of a shared `Arc<str>`.
+///
+/// It derefs to the `str` it stands for, and compares, hashes and prints like it too.
+#[derive(Clone)]
+pub struct ArcStr {
+    source: Arc<str>,
+    range: Range<usize>,
+}
+
+impl<D> OwnedStrSplit<D>
+where
+    D: StatefulDelimiter,
+{
+    pub fn new(haystack: impl Into<Arc<str>>, delimiter: D) -> Self {
+        let haystack = haystack.into();
+        Self {
+            state: Detached::new(haystack.len()),
+            haystack,
+            delimiter,
+        }
+    }
+
+    /// See [`StrSplit::limit`](crate::StrSplit::limit).
+    pub fn limit(mut self, n: usize) -> Self {
+        self.state.limit(n);
+        self
+    }
+
+    /// See [`StrSplit::inclusive`](crate::StrSplit::inclusive).
+    pub fn inclusive(mut self) -> Self {
+        self.state.inclusive();
+        self
+    }
+
+    /// See [`StrSplit::terminator`](crate::StrSplit::terminator).
+    pub fn terminator(mut self) -> Self {
+        self.state.terminator();
+        self
+    }
+
+    /// See [`StrSplit::skip_empty`](crate::StrSplit::skip_empty).
+    pub fn skip_empty(mut self) -> Self {
+        self.state.skip_empty();
+        self
+    }
+
+    /// See [`StrSplit::trim_matches`](crate::StrSplit::trim_matches).
+    pub fn trim_matches(mut self, pred: fn(char) -> bool) -> Self {
+        self.state.trim_matches(pred);
+        self
+    }
+
+    /// See [`StrSplit::trim`](crate::StrSplit::trim).
+    pub fn trim(self) -> Self {
+        self.trim_matches(char::is_whitespace)
+    }
+
+    /// The whole haystack, including the parts that were already split off.
+    pub fn haystack(&self) -> &Arc<str> {
+        &self.haystack
+    }
+
+    /// The byte range of the next field instead of an [`ArcStr`] for it.
+    pub fn next_range(&mut self) -> Option<Range<usize>> {
+        self.step(false)
+    }
+
+    /// The byte range of the next field from the back instead of an [`ArcStr`] for it.
+    pub fn next_back_range(&mut self) -> Option<Range<usize>> {
+        self.step(true)
+    }
+
+    fn step(&mut self, back: bool) -> Option<Range<usize>> {
+        let step = self
+            .state
+            .step(&self.haystack, 0, &mut self.delimiter, back)?;
+        Some(step.field)
+    }
+
+    fn arc_str(&self, range: Range<usize>) -> ArcStr {
+        ArcStr {
+            source: Arc::clone(&self.haystack),
+            range,
+        }
+    }
+}
+
+impl<D> Iterator for OwnedStrSplit<D>
+where
+    D: StatefulDelimiter,
+{
+    type Item = ArcStr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let range = self.next_range()?;
+        Some(self.arc_str(range))
+    }
+}
+
+impl<D> DoubleEndedIterator for OwnedStrSplit<D>
+where
+    D: StatefulDelimiter,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let range = self.next_back_range()?;
+        Some(self.arc_str(range))
+    }
+}
+
+impl ArcStr {
+    /// Where this sits in [`ArcStr::source`].
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// The whole string this is a part of.
+    pub fn source(&self) -> &Arc<str> {
+        &self.source
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source[self.range.clone()]
+    }
+}
+
+impl Deref for ArcStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for ArcStr {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for ArcStr {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for ArcStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for ArcStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl PartialEq for ArcStr {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for ArcStr {}
+
+impl PartialEq<str> for ArcStr {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for ArcStr {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialOrd for ArcStr {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ArcStr {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Hash for ArcStr {
+    fn hash<S: Hasher>(&self, state: &mut S) {
+        self.as_str().hash(state)
+    }
+}
+
+#[cfg(test)]
+fn columns(line: &str) -> OwnedStrSplit<char> {
+    let line = line.to_uppercase();
+    OwnedStrSplit::new(line, ',').trim()
+}
+
+#[test]
+fn owned() {
+    let split = columns("a, b ,c");
+    let fields: Vec<_> = split.clone().collect();
+    assert_eq!(fields, ["A", "B", "C"]);
+    assert!(Arc::ptr_eq(fields[0].source(), split.haystack()));
+    assert_eq!(fields[1].range(), 3..4);
+
+    let fields: Vec<_> = split.rev().collect();
+    assert_eq!(fields, ["C", "B", "A"]);
+}
+
+#[test]
+fn same_as_borrowed() {
+    let haystack = "a,b,,c,";
+    let cases: [fn(OwnedStrSplit<char>) -> OwnedStrSplit<char>; 5] = [
+        |split| split,
+        |split| split.limit(0),
+        |split| split.limit(2),
+        |split| split.terminator().skip_empty(),
+        |split| split.inclusive(),
+    ];
+    let borrowed: [fn(StrSplit<'_, char>) -> StrSplit<'_, char>; 5] = [
+        |split| split,
+        |split| split.limit(0),
+        |split| split.limit(2),
+        |split| split.terminator().skip_empty(),
+        |split| split.inclusive(),
+    ];
+    for (owned, borrowed) in cases.into_iter().zip(borrowed) {
+        let fields: Vec<_> = owned(OwnedStrSplit::new(haystack, ',')).collect();
+        let expected: Vec<_> = borrowed(StrSplit::new(haystack, ',')).collect();
+        assert_eq!(fields, expected);
+
+        let mut split = owned(OwnedStrSplit::new(Box::from(haystack), ','));
+        let mut expected = borrowed(StrSplit::new(haystack, ',')).spans();
+        while let Some((range, _)) = expected.next_back() {
+            assert_eq!(split.next_back_range(), Some(range));
+            if let Some((range, _)) = expected.next() {
+                assert_eq!(split.next_range(), Some(range));
+            }
+        }
+        assert_eq!(split.next_range(), None);
+    }
+}
@@ -0,0 +1,189 @@
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::Delimiter;
+#[cfg(any(feature = "alloc", test))]
+use crate::StrSplit;
+
+/// How a quote char can show up inside a quoted field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Escape {
+    /// Two quotes in a row stand for one, like CSV: `"say ""hi"""`.
+    Doubled,
+    /// A backslash escapes whatever comes after it: `"say \"hi\""`.
+    Backslash,
+}
+
+/// A separator that doesn't count inside quotes, for CSV-like fields such as `a,"b,c",d`.
+///
+/// Quoting only changes where the fields end, the fields themselves come out as they are in the
+/// haystack, quotes and all. Use [`Quoted::unquote`] or [`StrSplit::unquoted`] to get the
+/// contents.
+///
+/// A quote that's never closed runs to the end of the haystack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quoted {
+    separator: char,
+    quote: char,
+    escape: Escape,
+}
+
+impl Quoted {
+    pub fn new(separator: char, quote: char, escape: Escape) -> Self {
+        Self {
+            separator,
+            quote,
+            escape,
+        }
+    }
+
+    /// Comma separated, double quoted, quotes escaped by doubling them.
+    pub fn csv() -> Self {
+        Self::new(',', '"', Escape::Doubled)
+    }
+
+    /// Strips the quotes off `field` and resolves the escapes inside them.
+    ///
+    /// Only allocates if there's a quote in there to begin with.
+    #[cfg(feature = "alloc")]
+    pub fn unquote<'a>(&self, field: &'a str) -> Cow<'a, str> {
+        if !field.contains(self.quote) {
+            return Cow::Borrowed(field);
+        }
+        let mut unquoted = String::with_capacity(field.len());
+        let mut quoted = false;
+        let mut chars = field.chars().peekable();
+        while let Some(c) = chars.next() {
+            if !quoted {
+                if c == self.quote {
+                    quoted = true;
+                } else {
+                    unquoted.push(c);
+                }
+                continue;
+            }
+            match self.escape {
+                Escape::Backslash if c == '\\' => {
+                    if let Some(escaped) = chars.next() {
+                        unquoted.push(escaped);
+                    }
+                }
+                Escape::Doubled if c == self.quote && chars.peek() == Some(&self.quote) => {
+                    chars.next();
+                    unquoted.push(c);
+                }
+                _ if c == self.quote => quoted = false,
+                _ => unquoted.push(c),
+            }
+        }
+        Cow::Owned(unquoted)
+    }
+}
+
+// every search starts at the start of a field, and fields always start outside of quotes, so we
+// can just walk forward and keep track of whether we're in quotes as we go.
+impl Delimiter for Quoted {
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        let mut quoted = false;
+        let mut chars = s.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            if !quoted {
+                if c == self.separator {
+                    return Some((i, i + c.len_utf8()));
+                }
+                quoted = c == self.quote;
+                continue;
+            }
+            match self.escape {
+                Escape::Backslash if c == '\\' => {
+                    chars.next();
+                }
+                // a doubled quote is just a quote, stay in quotes.
+                Escape::Doubled
+                    if c == self.quote
+                        && matches!(chars.peek(), Some(&(_, q)) if q == self.quote) =>
+                {
+                    chars.next();
+                }
+                _ if c == self.quote => quoted = false,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    // no find_prev: whether a separator is quoted depends on everything in front of it, so we
+    // can't start from the back. the default walks forward from the start of remainder, which is
+    // the start of a field.
+}
+
+/// [`StrSplit`] with a [`Quoted`] delimiter that hands out the contents of each field, see
+/// [`StrSplit::unquoted`].
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct Unquoted<'haystack> {
+    split: StrSplit<'haystack, Quoted>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'haystack> StrSplit<'haystack, Quoted> {
+    /// Hand out every field with its quotes stripped and escapes resolved, see
+    /// [`Quoted::unquote`].
+    pub fn unquoted(self) -> Unquoted<'haystack> {
+        Unquoted { split: self }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'haystack> Iterator for Unquoted<'haystack> {
+    type Item = Cow<'haystack, str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let field = self.split.next()?;
+        Some(self.split.delimiter.unquote(field))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl DoubleEndedIterator for Unquoted<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let field = self.split.next_back()?;
+        Some(self.split.delimiter.unquote(field))
+    }
+}
+
+#[test]
+fn csv() {
+    let fields: Vec<_> = StrSplit::new(r#"a,"b,c",d"#, Quoted::csv()).collect();
+    assert_eq!(fields, vec!["a", r#""b,c""#, "d"]);
+    let fields: Vec<_> = StrSplit::new(r#"a,"b,c",d"#, Quoted::csv()).rev().collect();
+    assert_eq!(fields, vec!["d", r#""b,c""#, "a"]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn doubled_quotes() {
+    let haystack = r#""say ""hi, there""",x"#;
+    let fields: Vec<_> = StrSplit::new(haystack, Quoted::csv()).collect();
+    assert_eq!(fields, vec![r#""say ""hi, there""""#, "x"]);
+    let fields: Vec<_> = StrSplit::new(haystack, Quoted::csv()).unquoted().collect();
+    assert_eq!(fields, vec![r#"say "hi, there""#, "x"]);
+    assert!(matches!(fields[1], Cow::Borrowed(_)));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn backslash_escapes() {
+    let quoted = Quoted::new(';', '\'', Escape::Backslash);
+    let haystack = r"'it\'s; fine';\n;'a\\';b";
+    let fields: Vec<_> = StrSplit::new(haystack, quoted).unquoted().collect();
+    assert_eq!(fields, vec!["it's; fine", r"\n", r"a\", "b"]);
+}
+
+#[test]
+fn unterminated_quote() {
+    let fields: Vec<_> = StrSplit::new(r#"a,"b,c"#, Quoted::csv()).collect();
+    assert_eq!(fields, vec!["a", r#""b,c"#]);
+}
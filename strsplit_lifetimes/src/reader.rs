@@ -0,0 +1,358 @@
+use std::io::{self, BufRead};
+use std::ops::Range;
+use std::str::{self, Utf8Error};
+
+use crate::Delimiter;
+
+/// Splits whatever comes out of a [`BufRead`] into records, without reading it all in first.
+///
+/// Only the record being looked for is kept around, in a buffer that gets reused from one record
+/// to the next, so memory use depends on the longest record rather than on the whole input.
+/// [`ReaderSplit::next_record`] lends out each record as a `&str` into that buffer, and the
+/// [`Iterator`] impl hands out owned `String`s for when that's more convenient.
+///
+/// A delimiter that straddles two reads is found the same as if it was all in one piece. A
+/// match is taken as soon as more input can't change it: right away for a `'\n'` or `"\r\n"`,
+/// so a line is handed out as soon as it's complete, and for a [`MultiDelimiter`] once enough
+/// input is in to cover its longest pattern (see `Delimiter::max_match_chars`). Delimiters that
+/// can't tell how long a match gets have theirs taken once there's any input after it, or the
+/// input has ended. Input that isn't UTF-8 gives an [`io::ErrorKind::InvalidData`] error.
+///
+/// [`MultiDelimiter`]: crate::MultiDelimiter
+#[derive(Debug)]
+pub struct ReaderSplit<R, D> {
+    reader: R,
+    delimiter: D,
+    buf: RecordBuf,
+    eof: bool,
+    finished: bool,
+    terminator: bool,
+}
+
+impl<R, D> ReaderSplit<R, D>
+where
+    R: BufRead,
+    D: Delimiter,
+{
+    pub fn new(reader: R, delimiter: D) -> Self {
+        Self {
+            reader,
+            delimiter,
+            buf: RecordBuf::default(),
+            eof: false,
+            finished: false,
+            terminator: false,
+        }
+    }
+
+    /// Like [`StrSplit::terminator`](crate::StrSplit::terminator): no empty record after a
+    /// delimiter at the very end of the input, which is what you want for lines.
+    pub fn terminator(mut self) -> Self {
+        self.terminator = true;
+        self
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// The next record, borrowed from the internal buffer until you ask for another one.
+    pub fn next_record(&mut self) -> Option<io::Result<&str>> {
+        if self.finished {
+            return None;
+        }
+        loop {
+            match self.buf.next_match(&self.delimiter, self.eof) {
+                Ok(Some((start, end))) => {
+                    let record = self.buf.take(start, end);
+                    return Some(Ok(self.buf.record(record)));
+                }
+                Ok(None) if self.eof => {
+                    self.finished = true;
+                    if self.terminator && self.buf.is_empty() {
+                        return None;
+                    }
+                    let record = self.buf.take_rest();
+                    return Some(Ok(self.buf.record(record)));
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    self.finished = true;
+                    return Some(Err(io::Error::new(io::ErrorKind::InvalidData, e)));
+                }
+            }
+            if let Err(e) = self.fill() {
+                self.finished = true;
+                return Some(Err(e));
+            }
+        }
+    }
+
+    // one read, not as many as it takes to fill some amount: with a pipe or a socket a second
+    // one would sit there waiting for input we don't need yet.
+    fn fill(&mut self) -> io::Result<()> {
+        self.buf.compact();
+        let chunk = loop {
+            match self.reader.fill_buf() {
+                Ok(chunk) => break chunk,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        };
+        if chunk.is_empty() {
+            self.eof = true;
+        }
+        let n = chunk.len();
+        self.buf.extend(chunk);
+        self.reader.consume(n);
+        Ok(())
+    }
+}
+
+// the input ReaderSplit (and DelimiterCodec) have read but not handed out yet, and what they
+// already know about it, so no byte has to be checked for UTF-8 more than once, and with a
+// delimiter that has a max_match_chars, searched more than once either.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RecordBuf {
+    // everything up to the first byte that isn't (or isn't yet) UTF-8.
+    text: String,
+    // the rest: a char that's only partly here so far, or something that isn't UTF-8 at all.
+    pending: Vec<u8>,
+    // where in text the record we're looking for starts. everything before it is handed out.
+    start: usize,
+    // where in text the next search starts, no match we haven't seen can start before it.
+    searched: usize,
+    // how long the record at start is going to be at least, as of the last next_match that
+    // didn't find one.
+    min_len: usize,
+    // whether the record at start came right after a match, so an empty match right there
+    // doesn't count again.
+    matched: bool,
+}
+
+impl RecordBuf {
+    // how much of the input is still to be handed out.
+    pub(crate) fn len(&self) -> usize {
+        self.text.len() - self.start + self.pending.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn extend(&mut self, bytes: &[u8]) {
+        self.pending.extend_from_slice(bytes);
+        let valid = match str::from_utf8(&self.pending) {
+            Ok(s) => {
+                self.text.push_str(s);
+                self.pending.clear();
+                return;
+            }
+            Err(e) => e.valid_up_to(),
+        };
+        let s = str::from_utf8(&self.pending[..valid]).expect("valid_up_to");
+        self.text.push_str(s);
+        self.pending.drain(..valid);
+    }
+
+    // the next match after start, relative to start, once it's certain. until then the next read
+    // could still make it longer, or turn up a longer one that starts further left. it's certain
+    // once there are max_match_chars chars from where it starts, or at eof. a delimiter without
+    // a max_match_chars gets the benefit of the doubt once there's any input after the match.
+    // Ok(None) means no match yet, and at eof that the rest is the last record. an error means
+    // there's something that isn't UTF-8 before any match.
+    pub(crate) fn next_match<D: Delimiter>(
+        &mut self,
+        delimiter: &D,
+        eof: bool,
+    ) -> Result<Option<(usize, usize)>, Utf8Error> {
+        let s = &self.text[self.start..];
+        let from = self.searched - self.start;
+        let mut found = delimiter
+            .find_next(&s[from..])
+            .map(|(start, end)| (from + start, from + end));
+        if self.matched && found == Some((0, 0)) {
+            found = s.chars().next().and_then(|c| {
+                let skip = c.len_utf8();
+                let (start, end) = delimiter.find_next(&s[skip..])?;
+                Some((skip + start, skip + end))
+            });
+        }
+        // no match can run on into bytes that aren't UTF-8, so those end it too.
+        let invalid = self.invalid(eof);
+        let max = delimiter.max_match_chars();
+        if let Some((start, end)) = found {
+            let certain = match max {
+                Some(max) => s[start..].chars().take(max).count() == max,
+                None => end < s.len(),
+            };
+            if certain || eof || invalid.is_some() {
+                return Ok(Some((start, end)));
+            }
+        }
+        if let Some(e) = invalid {
+            return Err(e);
+        }
+        // a match we haven't seen yet has to reach past the end of s. if a match is at most max
+        // chars long, that means it starts somewhere in the last max - 1 chars.
+        self.searched = match max {
+            Some(max) => {
+                let tail = s.char_indices().rev().take(max.saturating_sub(1)).last();
+                let next = tail.map_or(s.len(), |(i, _)| i);
+                self.start + next.max(from)
+            }
+            None => self.start,
+        };
+        self.min_len = match (max, found) {
+            // a match we have but can't be sure of yet starts after searched too.
+            (Some(_), _) => self.searched - self.start,
+            (None, Some((start, _))) => start,
+            // a delimiter without a max_match_chars could still turn up a match further left,
+            // but there's no telling how much more input that takes.
+            (None, None) => s.len(),
+        };
+        Ok(None)
+    }
+
+    // see min_len the field, only means something after next_match gave Ok(None).
+    #[cfg_attr(not(feature = "async"), allow(dead_code))]
+    pub(crate) fn min_len(&self) -> usize {
+        self.min_len
+    }
+
+    // what's in pending can't turn into text anymore: it isn't UTF-8, or it's a char that was cut
+    // off by the end of the input.
+    fn invalid(&self, eof: bool) -> Option<Utf8Error> {
+        match str::from_utf8(&self.pending) {
+            Err(e) if eof || e.error_len().is_some() => Some(e),
+            _ => None,
+        }
+    }
+
+    // hands out the record that ends at `end` and moves on to after the match that ended it at
+    // `next`, both relative to start. gives back where the record is in text, see record.
+    pub(crate) fn take(&mut self, end: usize, next: usize) -> Range<usize> {
+        let record = self.start..self.start + end;
+        self.start += next;
+        self.searched = self.start;
+        self.matched = true;
+        record
+    }
+
+    // hands out everything that's left, for the end of the input.
+    pub(crate) fn take_rest(&mut self) -> Range<usize> {
+        let record = self.start..self.text.len();
+        self.start = self.text.len();
+        self.searched = self.start;
+        self.matched = false;
+        record
+    }
+
+    pub(crate) fn record(&self, record: Range<usize>) -> &str {
+        &self.text[record]
+    }
+
+    // drops what's been handed out already, so it doesn't pile up. this moves whatever is
+    // left to the front, so only do it once per read and not once per record.
+    pub(crate) fn compact(&mut self) {
+        self.text.drain(..self.start);
+        self.searched -= self.start;
+        self.start = 0;
+    }
+}
+
+impl<R, D> Iterator for ReaderSplit<R, D>
+where
+    R: BufRead,
+    D: Delimiter,
+{
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record()
+            .map(|record| record.map(ToOwned::to_owned))
+    }
+}
+
+#[cfg(test)]
+fn records<D: Delimiter>(input: &str, delimiter: D, capacity: usize) -> Vec<String> {
+    let reader = io::BufReader::with_capacity(capacity, input.as_bytes());
+    ReaderSplit::new(reader, delimiter)
+        .collect::<io::Result<_>>()
+        .unwrap()
+}
+
+#[test]
+fn matches_str_split() {
+    for input in ["a\r\nbb\r\n\r\nccc", "\r\n", "", "x", "ä\r\nö\r\nü\r\n"] {
+        let expected: Vec<_> = input.split("\r\n").collect();
+        for capacity in [1, 2, 3, 1024] {
+            assert_eq!(records(input, "\r\n", capacity), expected);
+        }
+    }
+    let expected: Vec<_> = "héllo".split("").collect();
+    assert_eq!(records("héllo", "", 1), expected);
+}
+
+#[test]
+fn straddling_longest_match() {
+    use crate::MultiDelimiter;
+    let delimiter = MultiDelimiter::new([";", ";;;"]);
+    assert_eq!(records("a;;;b;c", &delimiter, 1), vec!["a", "b", "c"]);
+}
+
+// hands out its input in one read, and panics if anyone asks for more.
+#[cfg(test)]
+struct OneRead(&'static [u8]);
+
+#[cfg(test)]
+impl io::Read for OneRead {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        unreachable!("ReaderSplit only uses fill_buf")
+    }
+}
+
+#[cfg(test)]
+impl BufRead for OneRead {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        assert!(!self.0.is_empty(), "read again after the whole record was in");
+        Ok(self.0)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.0 = &self.0[amt..];
+    }
+}
+
+#[test]
+fn no_read_ahead() {
+    let mut split = ReaderSplit::new(OneRead(b"hello\n"), '\n');
+    assert_eq!(split.next_record().unwrap().unwrap(), "hello");
+    let mut split = ReaderSplit::new(OneRead(b"a\r\nb\r\n"), "\r\n");
+    assert_eq!(split.next_record().unwrap().unwrap(), "a");
+    assert_eq!(split.next_record().unwrap().unwrap(), "b");
+    let delimiter = crate::MultiDelimiter::new(["\n", "\r\n"]);
+    let mut split = ReaderSplit::new(OneRead(b"ping\r\n"), &delimiter);
+    assert_eq!(split.next_record().unwrap().unwrap(), "ping");
+}
+
+#[test]
+fn terminator_and_lending() {
+    let mut split = ReaderSplit::new("a\nb\n".as_bytes(), '\n').terminator();
+    assert_eq!(split.next_record().unwrap().unwrap(), "a");
+    assert_eq!(split.next_record().unwrap().unwrap(), "b");
+    assert!(split.next_record().is_none());
+    assert!(ReaderSplit::new(&b""[..], '\n')
+        .terminator()
+        .next()
+        .is_none());
+}
+
+#[test]
+fn invalid_utf8() {
+    let mut split = ReaderSplit::new(&b"ok\n\xff\n"[..], '\n');
+    assert_eq!(split.next_record().unwrap().unwrap(), "ok");
+    let err = split.next_record().unwrap().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(split.next_record().is_none());
+}
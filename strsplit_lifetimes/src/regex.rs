@@ -0,0 +1,131 @@
+use ::regex::{CaptureMatches, Captures, Regex};
+
+use crate::Delimiter;
+
+// a regex only ever sees the part of the haystack that's left, so anchors and word boundaries
+// (^, \b, ...) are checked against the start of that, not against the start of the haystack.
+// if you need those, CaptureSplit always looks at the whole haystack.
+// empty matches are fine, StrSplit makes sure they don't get it stuck.
+impl Delimiter for Regex {
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        self.find(s).map(|m| (m.start(), m.end()))
+    }
+
+    // regexes can't search backwards, so the last match is the last one going forwards.
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        self.find_iter(s).last().map(|m| (m.start(), m.end()))
+    }
+}
+
+impl Delimiter for &Regex {
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        (**self).find_next(s)
+    }
+
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        (**self).find_prev(s)
+    }
+}
+
+/// Splits a haystack on a regex and hands out the capture groups of every separator along with
+/// the field in front of it.
+///
+/// Each item is a field and the captures of the separator that ended it, `None` for the last
+/// field. Capture offsets are relative to the start of the haystack.
+///
+/// ```
+/// # use regex::Regex;
+/// # use strsplit::CaptureSplit;
+/// let separator = Regex::new(r"\s*([,;])\s*").unwrap();
+/// let mut split = CaptureSplit::new(&separator, "a , b;c");
+/// let (field, captures) = split.next().unwrap();
+/// assert_eq!(field, "a");
+/// assert_eq!(&captures.unwrap()[1], ",");
+/// ```
+#[derive(Debug)]
+pub struct CaptureSplit<'r, 'haystack> {
+    haystack: &'haystack str,
+    captures: CaptureMatches<'r, 'haystack>,
+    // where the field we hand out next starts, None once the last one is out.
+    start: Option<usize>,
+}
+
+impl<'r, 'haystack> CaptureSplit<'r, 'haystack> {
+    /// Splits `haystack` on every match of `regex`.
+    pub fn new(regex: &'r Regex, haystack: &'haystack str) -> Self {
+        Self {
+            haystack,
+            captures: regex.captures_iter(haystack),
+            start: Some(0),
+        }
+    }
+}
+
+impl<'haystack> Iterator for CaptureSplit<'_, 'haystack> {
+    type Item = (&'haystack str, Option<Captures<'haystack>>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.start?;
+        match self.captures.next() {
+            Some(captures) => {
+                let separator = captures.get(0).expect("group 0 is always the whole match");
+                self.start = Some(separator.end());
+                Some((&self.haystack[start..separator.start()], Some(captures)))
+            }
+            None => {
+                self.start = None;
+                Some((&self.haystack[start..], None))
+            }
+        }
+    }
+}
+
+#[test]
+fn regex_delimiter() {
+    let separator = Regex::new(r"\s*,\s*").unwrap();
+    let fields: Vec<_> = crate::StrSplit::new("a , b,c  ,d", &separator).collect();
+    assert_eq!(fields, vec!["a", "b", "c", "d"]);
+    let fields: Vec<_> = crate::StrSplit::new("a;|b||c", Regex::new("[;|]+").unwrap())
+        .rev()
+        .collect();
+    assert_eq!(fields, vec!["c", "b", "a"]);
+}
+
+#[test]
+fn empty_matches() {
+    for (pattern, haystack) in [("", "abc"), ("x*", "axxbc"), ("x*", ""), ("x?", "xx")] {
+        let regex = Regex::new(pattern).unwrap();
+        let fields: Vec<_> = crate::StrSplit::new(haystack, &regex).collect();
+        assert_eq!(
+            fields,
+            regex.split(haystack).collect::<Vec<_>>(),
+            "{pattern:?} on {haystack:?}",
+        );
+    }
+}
+
+#[test]
+fn captures() {
+    let separator = Regex::new(r"\s*(?<op>[+-])\s*").unwrap();
+    let split: Vec<_> = CaptureSplit::new(&separator, "1 + 2-3")
+        .map(|(field, captures)| (field, captures.map(|c| c["op"].to_string())))
+        .collect();
+    assert_eq!(
+        split,
+        vec![
+            ("1", Some("+".to_string())),
+            ("2", Some("-".to_string())),
+            ("3", None),
+        ]
+    );
+}
+
+#[test]
+fn captures_offsets() {
+    let separator = Regex::new("(,)").unwrap();
+    let mut split = CaptureSplit::new(&separator, "ab,c");
+    let (_, captures) = split.next().unwrap();
+    assert_eq!(captures.unwrap().get(1).unwrap().range(), 2..3);
+    assert_eq!(split.next().map(|(field, _)| field), Some("c"));
+    assert!(split.next().is_none());
+}
@@ -0,0 +1,131 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::Delimiter;
+
+/// Matches each run of Unicode whitespace, so `"a \u{3000} b"` splits into `"a"` and `"b"`.
+///
+/// Add [`StrSplit::skip_empty`](crate::StrSplit::skip_empty) to also drop the empty fields from
+/// whitespace at either end, and you've got `str::split_whitespace`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Whitespace;
+
+/// Matches the empty string at every grapheme cluster boundary (extended, per UAX #29), except
+/// at the very start and end, so every field is one user-perceived character.
+///
+/// A char plus its combining marks, a flag, or an emoji built out of several with zero width
+/// joiners is never cut in half.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GraphemeBoundary;
+
+/// Matches the empty string at every word boundary (per UAX #29), except at the very start and
+/// end.
+///
+/// Every field is then a word, or whatever is between words, like `" "` or `", "`. Add
+/// [`StrSplit::trim`](crate::StrSplit::trim) and
+/// [`StrSplit::skip_empty`](crate::StrSplit::skip_empty) to get rid of the whitespace ones.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WordBoundary;
+
+impl Delimiter for Whitespace {
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        let start = s.find(char::is_whitespace)?;
+        let end = s.len() - s[start..].trim_start_matches(char::is_whitespace).len();
+        Some((start, end))
+    }
+
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        let end = s.trim_end_matches(|c: char| !c.is_whitespace()).len();
+        if end == 0 {
+            return None;
+        }
+        let start = s[..end].trim_end_matches(char::is_whitespace).len();
+        Some((start, end))
+    }
+}
+
+// remainder always starts and ends on a boundary (that's where StrSplit cut it), so finding the
+// boundaries in it gives the same ones as in the whole haystack. the one at 0 and the one at
+// s.len() are skipped, a field there would always be empty.
+
+impl Delimiter for GraphemeBoundary {
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        let (at, _) = s.grapheme_indices(true).nth(1)?;
+        Some((at, at))
+    }
+
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        let (at, _) = s.grapheme_indices(true).next_back()?;
+        (at > 0).then_some((at, at))
+    }
+}
+
+impl Delimiter for WordBoundary {
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        let (at, _) = s.split_word_bound_indices().nth(1)?;
+        Some((at, at))
+    }
+
+    fn find_prev(&self, s: &str) -> Option<(usize, usize)> {
+        let (at, _) = s.split_word_bound_indices().next_back()?;
+        (at > 0).then_some((at, at))
+    }
+}
+
+#[cfg(test)]
+use crate::StrSplit;
+
+#[test]
+fn whitespace() {
+    let haystack = " a\u{3000}\u{2003}b\tc \n";
+    let fields: Vec<_> = StrSplit::new(haystack, Whitespace).collect();
+    assert_eq!(fields, vec!["", "a", "b", "c", ""]);
+    let fields: Vec<_> = StrSplit::new(haystack, Whitespace)
+        .skip_empty()
+        .rev()
+        .collect();
+    let expected: Vec<_> = haystack.split_whitespace().rev().collect();
+    assert_eq!(fields, expected);
+}
+
+#[test]
+fn graphemes() {
+    let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+    let haystack = format!("e\u{301}{family}\u{1F1FA}\u{1F1F8}\u{1F1EB}\u{1F1F7}\r\nx");
+    let expected: Vec<_> = haystack.graphemes(true).collect();
+    assert_eq!(expected.len(), 6);
+    let fields: Vec<_> = StrSplit::new(&haystack, GraphemeBoundary).collect();
+    assert_eq!(fields, expected);
+    let mut fields: Vec<_> = StrSplit::new(&haystack, GraphemeBoundary).rev().collect();
+    fields.reverse();
+    assert_eq!(fields, expected);
+
+    assert_eq!(
+        StrSplit::new("", GraphemeBoundary).collect::<Vec<_>>(),
+        vec![""]
+    );
+}
+
+#[test]
+fn words() {
+    let haystack = concat!(
+        "The quick (\u{201C}brown\u{201D}) fox can't jump 32.3 feet, right? ",
+        "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467} cafe\u{301}",
+    );
+    let expected: Vec<_> = haystack.split_word_bounds().collect();
+    let fields: Vec<_> = StrSplit::new(haystack, WordBoundary).collect();
+    assert_eq!(fields, expected);
+    let mut fields: Vec<_> = StrSplit::new(haystack, WordBoundary).rev().collect();
+    fields.reverse();
+    assert_eq!(fields, expected);
+
+    let words: Vec<_> = StrSplit::new(haystack, WordBoundary)
+        .trim()
+        .skip_empty()
+        .collect();
+    assert_eq!(
+        words[..6],
+        ["The", "quick", "(", "\u{201C}", "brown", "\u{201D}"]
+    );
+    assert!(words.contains(&"can't") && words.contains(&"32.3"));
+    assert!(words.contains(&"cafe\u{301}"));
+}
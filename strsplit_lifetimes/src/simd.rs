@@ -0,0 +1,106 @@
+// fast paths for the delimiters that boil down to looking for one, two or three bytes, or for a
+// short byte string. memchr picks sse2/avx2 (or neon, or whatever else it has) at runtime and
+// falls back to a plain loop where there's nothing better.
+
+// the ascii chars of a char delimiter, if there are few enough of them for memchr3.
+// any ascii byte in utf-8 is always that char and never part of a longer one, so a byte search
+// finds exactly the same matches as walking the chars.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Needles {
+    One(u8),
+    Two(u8, u8),
+    Three(u8, u8, u8),
+}
+
+impl Needles {
+    pub(crate) fn new(chars: &[char]) -> Option<Self> {
+        if !chars.iter().all(char::is_ascii) {
+            return None;
+        }
+        match *chars {
+            [a] => Some(Needles::One(a as u8)),
+            [a, b] => Some(Needles::Two(a as u8, b as u8)),
+            [a, b, c] => Some(Needles::Three(a as u8, b as u8, c as u8)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn find(self, s: &[u8]) -> Option<usize> {
+        match self {
+            Needles::One(a) => memchr::memchr(a, s),
+            Needles::Two(a, b) => memchr::memchr2(a, b, s),
+            Needles::Three(a, b, c) => memchr::memchr3(a, b, c, s),
+        }
+    }
+
+    pub(crate) fn rfind(self, s: &[u8]) -> Option<usize> {
+        match self {
+            Needles::One(a) => memchr::memrchr(a, s),
+            Needles::Two(a, b) => memchr::memrchr2(a, b, s),
+            Needles::Three(a, b, c) => memchr::memrchr3(a, b, c, s),
+        }
+    }
+}
+
+// a single char. ascii is one memchr, anything else we look for by its leading byte, which only
+// ever shows up at the start of that one char, and then check the rest of it.
+pub(crate) fn find_char(c: char, s: &[u8]) -> Option<usize> {
+    let mut buf = [0; 4];
+    let needle = c.encode_utf8(&mut buf).as_bytes();
+    let mut offset = 0;
+    while let Some(i) = memchr::memchr(needle[0], &s[offset..]) {
+        if s[offset + i..].starts_with(needle) {
+            return Some(offset + i);
+        }
+        offset += i + 1;
+    }
+    None
+}
+
+pub(crate) fn rfind_char(c: char, s: &[u8]) -> Option<usize> {
+    let mut buf = [0; 4];
+    let needle = c.encode_utf8(&mut buf).as_bytes();
+    let mut end = s.len();
+    while let Some(i) = memchr::memrchr(needle[0], &s[..end]) {
+        if s[i..].starts_with(needle) {
+            return Some(i);
+        }
+        end = i;
+    }
+    None
+}
+
+pub(crate) fn find_bytes(needle: &[u8], s: &[u8]) -> Option<usize> {
+    memchr::memmem::find(s, needle)
+}
+
+pub(crate) fn rfind_bytes(needle: &[u8], s: &[u8]) -> Option<usize> {
+    memchr::memmem::rfind(s, needle)
+}
+
+#[test]
+fn same_as_scalar() {
+    use crate::{ByteSplit, StrSplit};
+
+    let haystack = "a,b;;c|dé,,→f,g|";
+    for delimiter in [&[','][..], &[',', ';'], &[',', ';', '|'], &['→'], &[',', 'é']] {
+        let scalar: Vec<_> = StrSplit::new(haystack, |c: char| delimiter.contains(&c)).collect();
+        assert_eq!(StrSplit::new(haystack, delimiter).collect::<Vec<_>>(), scalar);
+        let scalar: Vec<_> = StrSplit::new(haystack, |c: char| delimiter.contains(&c))
+            .rev()
+            .collect();
+        assert_eq!(StrSplit::new(haystack, delimiter).rev().collect::<Vec<_>>(), scalar);
+    }
+    for c in [',', '→', 'x'] {
+        let scalar: Vec<_> = StrSplit::new(haystack, |d: char| d == c).collect();
+        assert_eq!(StrSplit::new(haystack, c).collect::<Vec<_>>(), scalar);
+        let scalar: Vec<_> = StrSplit::new(haystack, |d: char| d == c).rev().collect();
+        assert_eq!(StrSplit::new(haystack, c).rev().collect::<Vec<_>>(), scalar);
+    }
+
+    let haystack = haystack.as_bytes();
+    let fields: Vec<_> = ByteSplit::from_bytes(haystack, b",,").collect();
+    assert_eq!(fields, vec![&b"a,b;;c|d\xc3\xa9"[..], "→f,g|".as_bytes()]);
+    let fields: Vec<_> = ByteSplit::from_bytes(haystack, b'|').rev().collect();
+    assert_eq!(fields, vec![&b""[..], "dé,,→f,g".as_bytes(), b"a,b;;c"]);
+}
@@ -0,0 +1,29 @@
+// the crate is no_std without the std feature, and only needs alloc for the delimiters that
+// allocate. nothing else in the test suite builds it that way, so check that it still does.
+
+use std::path::Path;
+use std::process::Command;
+
+fn check(features: &[&str]) {
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("no_std");
+    let status = Command::new(env!("CARGO"))
+        .args(["check", "--lib", "--no-default-features"])
+        .args(features)
+        .arg("--target-dir")
+        .arg(target_dir)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env("RUSTFLAGS", "-D warnings")
+        .status()
+        .expect("cargo check didn't run");
+    assert!(status.success(), "no_std build with {features:?} failed");
+}
+
+#[test]
+fn without_alloc() {
+    check(&[]);
+}
+
+#[test]
+fn with_alloc() {
+    check(&["--features", "alloc"]);
+}